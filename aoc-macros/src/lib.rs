@@ -0,0 +1,152 @@
+//! The `#[aoc]` attribute, which registers days and alternative solvers with the runner in
+//! `advent_of_code_rust`, so days don't have to be listed anywhere by hand.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::Parser;
+use syn::{meta, parse_macro_input, Error, Item, ItemFn, ItemImpl, LitInt, LitStr};
+
+#[derive(Default)]
+struct Args {
+    day: Option<LitInt>,
+    part: Option<LitInt>,
+    name: Option<LitStr>,
+}
+
+impl Args {
+    fn parse(attr: TokenStream) -> Result<Self, Error> {
+        let mut args = Args::default();
+        let parser = meta::parser(|meta| {
+            if meta.path.is_ident("day") {
+                args.day = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("part") {
+                args.part = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("name") {
+                args.name = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported argument"));
+            }
+            Ok(())
+        });
+        parser.parse(attr)?;
+        Ok(args)
+    }
+}
+
+fn number(
+    lit: &Option<LitInt>,
+    what: &str,
+    range: std::ops::RangeInclusive<u8>,
+) -> Result<u8, Error> {
+    let lit = lit.as_ref().ok_or_else(|| {
+        Error::new(
+            proc_macro2::Span::call_site(),
+            format!("missing `{} = ...`", what),
+        )
+    })?;
+    let value: u8 = lit.base10_parse()?;
+    if !range.contains(&value) {
+        return Err(Error::new(
+            lit.span(),
+            format!("{} must be in {}..={}", what, range.start(), range.end()),
+        ));
+    }
+    Ok(value)
+}
+
+/// Registers a day or an alternative solver.
+///
+/// On a day's `impl Solution for DayNN` block, `#[aoc(day = 5)]` adds the day to
+/// `advent_of_code_rust::days()`.
+///
+/// On a function, `#[aoc(day = 5, part = 2, name = "fast")]` adds a named alternative to
+/// the day's own part 2, picked with `aoc run --solver`. The function takes a reference to
+/// the day's parsed input and returns `Result<impl Display, AocError>`; taking any other
+/// input, or naming a day that is not registered, fails to compile.
+#[proc_macro_attribute]
+pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = match Args::parse(attr) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let expanded = match parse_macro_input!(item as Item) {
+        Item::Impl(item) => register_day(args, item),
+        Item::Fn(item) => register_solver(args, item),
+        item => Err(Error::new_spanned(
+            item,
+            "#[aoc] goes on a `Solution` impl or on a solver function",
+        )),
+    };
+    expanded.unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn register_day(args: Args, item: ItemImpl) -> Result<proc_macro2::TokenStream, Error> {
+    if let Some(part) = &args.part {
+        return Err(Error::new(
+            part.span(),
+            "a day is registered as a whole; `part` is for solver functions",
+        ));
+    }
+    if let Some(name) = &args.name {
+        return Err(Error::new(
+            name.span(),
+            "a day is registered as a whole; `name` is for solver functions",
+        ));
+    }
+    let day = number(&args.day, "day", 1..=25)?;
+    let ty = &item.self_ty;
+
+    Ok(quote! {
+        #item
+
+        impl crate::registry::DayInput<#day> for crate::registry::Days {
+            type Input = <#ty as crate::solution::Solution>::Input;
+        }
+
+        ::inventory::submit! {
+            crate::Day {
+                number: #day,
+                solution: &#ty,
+            }
+        }
+    })
+}
+
+fn register_solver(args: Args, func: ItemFn) -> Result<proc_macro2::TokenStream, Error> {
+    let day = number(&args.day, "day", 1..=25)?;
+    let part = number(&args.part, "part", 1..=2)?;
+    let name = match &args.name {
+        None => {
+            return Err(Error::new(
+                proc_macro2::Span::call_site(),
+                "missing `name = ...`: the day's own parts are its `Solution` impl",
+            ))
+        }
+        Some(name) if name.value() == "default" => {
+            return Err(Error::new(
+                name.span(),
+                "`default` is the day's own `Solution` impl",
+            ))
+        }
+        Some(name) => name.value(),
+    };
+    let ident = &func.sig.ident;
+
+    Ok(quote! {
+        #func
+
+        ::inventory::submit! {
+            crate::registry::Solver {
+                day: #day,
+                part: #part,
+                name: #name,
+                solve: |input| {
+                    crate::registry::erase_solve::<
+                        <crate::registry::Days as crate::registry::DayInput<#day>>::Input,
+                        _,
+                    >(input, #ident)
+                },
+            }
+        }
+    })
+}
@@ -0,0 +1,299 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::AocError;
+use crate::solution::{self, ParseMode};
+use crate::Day;
+
+/// Answers file checked into the repository, relative to the working directory.
+pub const ANSWERS_FILE: &str = "answers.json";
+
+const VERSION: u32 = 1;
+
+/// Known-good answers, keyed by day (`day05`), then input name (`data`), then part.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnswerDb {
+    pub version: u32,
+    pub days: BTreeMap<String, BTreeMap<String, PartAnswers>>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+    /// Parts whose answer the site confirmed. The others were recorded by `aoc verify
+    /// --record` or from an example, and may still be wrong.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub accepted: BTreeSet<u8>,
+    /// Answers the site said were wrong, by part.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rejected: BTreeMap<u8, Vec<Rejected>>,
+}
+
+/// Whether the site said a wrong answer was too high or too low.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hint::TooHigh => write!(f, "too high"),
+            Hint::TooLow => write!(f, "too low"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rejected {
+    pub answer: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Hint>,
+}
+
+impl PartAnswers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            _ => self.part2.as_deref(),
+        }
+    }
+
+    /// Records `answer` for `part`; replacing it with a different one drops the confirmation.
+    fn set(&mut self, part: u8, answer: String) {
+        if self.get(part) != Some(answer.as_str()) {
+            self.accepted.remove(&part);
+        }
+        match part {
+            1 => self.part1 = Some(answer),
+            _ => self.part2 = Some(answer),
+        }
+    }
+}
+
+impl Default for AnswerDb {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            days: BTreeMap::new(),
+        }
+    }
+}
+
+impl AnswerDb {
+    /// Loads the answers file, starting from an empty database if it does not exist yet.
+    /// A file written in another format version is refused rather than misread.
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        let db: Self = match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        if db.version != VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "{} is version {}, but only version {} can be read",
+                    path.display(),
+                    db.version,
+                    VERSION
+                ),
+            )
+            .into());
+        }
+        Ok(db)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AocError> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.days.get(&day_key(day))?.get(input)?.get(part)
+    }
+
+    /// The recorded answer, but only if the site confirmed it.
+    pub fn accepted(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        let answers = self.days.get(&day_key(day))?.get(input)?;
+        answers
+            .accepted
+            .contains(&part)
+            .then(|| answers.get(part))
+            .flatten()
+    }
+
+    pub fn record(&mut self, day: u8, input: &str, part: u8, answer: String) {
+        self.entry(day, input).set(part, answer);
+    }
+
+    /// Records an answer the site said was right.
+    pub fn accept(&mut self, day: u8, input: &str, part: u8, answer: String) {
+        let answers = self.entry(day, input);
+        answers.set(part, answer);
+        answers.accepted.insert(part);
+    }
+
+    fn entry(&mut self, day: u8, input: &str) -> &mut PartAnswers {
+        self.days
+            .entry(day_key(day))
+            .or_default()
+            .entry(input.to_string())
+            .or_default()
+    }
+
+    pub fn rejected(&self, day: u8, input: &str, part: u8) -> &[Rejected] {
+        self.days
+            .get(&day_key(day))
+            .and_then(|inputs| inputs.get(input))
+            .and_then(|answers| answers.rejected.get(&part))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn reject(&mut self, day: u8, input: &str, part: u8, answer: String, hint: Option<Hint>) {
+        self.entry(day, input)
+            .rejected
+            .entry(part)
+            .or_default()
+            .push(Rejected { answer, hint });
+    }
+}
+
+pub fn day_key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+/// Outcome of comparing a freshly computed answer with the recorded one.
+#[derive(Debug)]
+pub enum Verdict {
+    Match(String),
+    Changed {
+        expected: String,
+        actual: String,
+    },
+    /// No answer is recorded yet for this day, input and part.
+    New(String),
+    Failed(AocError),
+}
+
+impl Verdict {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Verdict::Match(_) | Verdict::New(_))
+    }
+}
+
+/// Solves both parts of `day` and compares them with the answers recorded for `input_name`.
+pub fn verify_day(
+    db: &AnswerDb,
+    day: &Day,
+    input_name: &str,
+    input: &str,
+) -> Result<[Verdict; 2], AocError> {
+    let answers = solution::solve(day.solution, input, None, ParseMode::Strict)?;
+
+    let verdict = |part: u8, answer: Option<Result<String, AocError>>| {
+        let actual = match answer {
+            Some(Ok(actual)) => actual,
+            Some(Err(e)) => return Verdict::Failed(e),
+            None => unreachable!("both parts are solved"),
+        };
+        match db.get(day.number, input_name, part) {
+            Some(expected) if expected == actual => Verdict::Match(actual),
+            Some(expected) => Verdict::Changed {
+                expected: expected.to_string(),
+                actual,
+            },
+            None => Verdict::New(actual),
+        }
+    };
+
+    Ok([verdict(1, answers.part1), verdict(2, answers.part2)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut db = AnswerDb::default();
+        db.record(5, "sample", 1, "CMZ".to_string());
+        db.record(5, "sample", 2, "MCD".to_string());
+
+        let json = serde_json::to_string(&db).unwrap();
+        let loaded: AnswerDb = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, db);
+        assert_eq!(loaded.get(5, "sample", 2), Some("MCD"));
+        assert_eq!(loaded.get(5, "data", 2), None);
+    }
+
+    #[test]
+    fn test_load_checks_the_version() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.json", std::process::id()));
+        let mut db = AnswerDb::default();
+        db.record(1, "data", 1, "24000".to_string());
+        db.save(&path).unwrap();
+        assert_eq!(AnswerDb::load(&path).unwrap(), db);
+
+        db.version = VERSION + 1;
+        db.save(&path).unwrap();
+        let err = AnswerDb::load(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.to_string().contains("version 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_rejected_round_trip() {
+        let mut db = AnswerDb::default();
+        db.reject(1, "data", 2, "1234".to_string(), Some(Hint::TooLow));
+
+        let json = serde_json::to_string(&db).unwrap();
+        assert!(json.contains(r#""rejected":{"2":[{"answer":"1234","hint":"too_low"}]}"#));
+        let loaded: AnswerDb = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.rejected(1, "data", 2)[0].hint, Some(Hint::TooLow));
+        assert!(loaded.rejected(1, "data", 1).is_empty());
+    }
+
+    #[test]
+    fn test_only_confirmed_answers_are_accepted() {
+        let mut db = AnswerDb::default();
+        db.record(1, "data", 1, "24000".to_string());
+        assert_eq!(db.accepted(1, "data", 1), None);
+
+        db.accept(1, "data", 1, "24000".to_string());
+        assert_eq!(db.accepted(1, "data", 1), Some("24000"));
+        let json = serde_json::to_string(&db).unwrap();
+        assert!(json.contains(r#""accepted":[1]"#));
+
+        db.record(1, "data", 1, "24000".to_string());
+        assert_eq!(db.accepted(1, "data", 1), Some("24000"));
+        db.record(1, "data", 1, "25000".to_string());
+        assert_eq!(db.accepted(1, "data", 1), None);
+        assert_eq!(db.get(1, "data", 1), Some("25000"));
+    }
+
+    #[test]
+    fn test_verify_day() {
+        let day = crate::find_day("5").unwrap();
+        let input = include_str!("./day05/sample.txt");
+        let mut db = AnswerDb::default();
+        db.record(5, "sample", 1, "CMZ".to_string());
+        db.record(5, "sample", 2, "XYZ".to_string());
+
+        let [part1, part2] = verify_day(&db, day, "sample", input).unwrap();
+        assert!(matches!(part1, Verdict::Match(ref a) if a == "CMZ"));
+        assert!(matches!(part2, Verdict::Changed { ref actual, .. } if actual == "MCD"));
+        assert!(!part2.is_ok());
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::AocError;
+use crate::solution::ParseMode;
+use crate::Day;
+
+/// Timing summary for one stage over every iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    #[serde(rename = "min_ns", serialize_with = "as_nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", serialize_with = "as_nanos")]
+    pub median: Duration,
+    #[serde(rename = "mean_ns", serialize_with = "as_nanos")]
+    pub mean: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let n = samples.len().max(1);
+        let median = match samples.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 0 => (samples[len / 2 - 1] + samples[len / 2]) / 2,
+            len => samples[len / 2],
+        };
+
+        Self {
+            min: samples.first().copied().unwrap_or_default(),
+            median,
+            mean: samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DayBench {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayBench {
+    /// Median time for a full run: parse plus both parts.
+    pub fn total(&self) -> Duration {
+        self.parse.median + self.part1.median + self.part2.median
+    }
+}
+
+/// A benchmark run, as exported with `aoc bench --json`.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub label: Option<String>,
+    pub days: Vec<DayBench>,
+}
+
+/// Times parsing and each part of `day` separately, `iterations` times each.
+pub fn bench_day(day: &Day, input: &str, iterations: usize) -> Result<DayBench, AocError> {
+    let mut parse = Vec::with_capacity(iterations);
+    let mut part1 = Vec::with_capacity(iterations);
+    let mut part2 = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = day.solution.parse(input, ParseMode::Strict)?;
+        parse.push(start.elapsed());
+
+        let start = Instant::now();
+        day.solution.part1(parsed.as_ref())?;
+        part1.push(start.elapsed());
+
+        let start = Instant::now();
+        day.solution.part2(parsed.as_ref())?;
+        part2.push(start.elapsed());
+    }
+
+    Ok(DayBench {
+        day: day.number,
+        iterations,
+        parse: Stats::from_samples(&mut parse),
+        part1: Stats::from_samples(&mut part1),
+        part2: Stats::from_samples(&mut part2),
+    })
+}
+
+pub fn format_table(results: &[DayBench]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<5} {:<7} {:>12} {:>12} {:>12}",
+        "day", "stage", "min", "median", "mean"
+    );
+    for r in results {
+        for (stage, stats) in [("parse", r.parse), ("part 1", r.part1), ("part 2", r.part2)] {
+            let _ = writeln!(
+                out,
+                "{:<5} {:<7} {:>12} {:>12} {:>12}",
+                format!("{:02}", r.day),
+                stage,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.mean),
+            );
+        }
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{:<5} {:>12}", "day", "total");
+    for r in results {
+        let _ = writeln!(
+            out,
+            "{:<5} {:>12}",
+            format!("{:02}", r.day),
+            format!("{:.2?}", r.total())
+        );
+    }
+    let total: Duration = results.iter().map(|r| r.total()).sum();
+    let _ = write!(out, "{:<5} {:>12}", "all", format!("{:.2?}", total));
+    out
+}
+
+fn as_nanos<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut samples = [5, 1, 3, 2].map(Duration::from_micros);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+        assert_eq!(stats.mean, Duration::from_nanos(2750));
+    }
+
+    #[test]
+    fn test_bench_day() {
+        let day = crate::find_day("1").unwrap();
+        let result = bench_day(day, include_str!("./day01/sample.txt"), 3).unwrap();
+        assert_eq!(result.day, 1);
+        assert_eq!(result.iterations, 3);
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert!(json["parse"]["median_ns"].is_u64());
+    }
+}
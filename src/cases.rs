@@ -0,0 +1,107 @@
+//! Named inputs kept next to each day's solver, e.g. `src/day06/sample2.txt`, each with an
+//! `sample2.answers.json` sidecar holding the expected answers:
+//!
+//! ```json
+//! { "part1": "5", "part2": "23" }
+//! ```
+//!
+//! A part left out of the sidecar is not checked, which is how inputs that only make
+//! sense for one part (or that are expected to fail the other) are described.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{self, AnswerDb, PartAnswers, Verdict};
+use crate::error::AocError;
+use crate::input;
+use crate::Day;
+
+pub const SIDECAR_SUFFIX: &str = ".answers.json";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub input: PathBuf,
+    pub expected: PartAnswers,
+}
+
+pub fn day_dir(root: &Path, day: u8) -> PathBuf {
+    root.join(format!("src/day{:02}", day))
+}
+
+/// Every `*.txt` input in the day directory that has a sidecar, sorted by name.
+pub fn discover(root: &Path, day: u8) -> Result<Vec<Case>, AocError> {
+    let dir = day_dir(root, day);
+    let mut cases = vec![];
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("txt") {
+            continue;
+        }
+        let name = input::name(path.to_str());
+        let sidecar = dir.join(format!("{}{}", name, SIDECAR_SUFFIX));
+        let expected = match fs::read_to_string(&sidecar) {
+            Ok(json) => serde_json::from_str(&json)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        cases.push(Case {
+            name,
+            input: path,
+            expected,
+        });
+    }
+    cases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(cases)
+}
+
+/// Solves `case` and compares each part that has an expected answer.
+pub fn check(day: &Day, case: &Case) -> Result<Vec<(u8, Verdict)>, AocError> {
+    let input = input::load(day.number, case.input.to_str())?;
+    let mut db = AnswerDb::default();
+    for part in 1..=2 {
+        if let Some(expected) = case.expected.get(part) {
+            db.record(day.number, &case.name, part, expected.to_string());
+        }
+    }
+
+    let verdicts = answers::verify_day(&db, day, &case.name, &input)?;
+    Ok((1..=2)
+        .zip(verdicts)
+        .filter(|(part, _)| case.expected.get(*part).is_some())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let names: Vec<_> = discover(root, 6)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(
+            names,
+            ["sample", "sample2", "sample3", "sample4", "sample5"]
+        );
+    }
+
+    #[test]
+    fn test_unchecked_part_is_skipped() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let day = crate::find_day("1").unwrap();
+        let case = discover(root, 1)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.name == "single_elf")
+            .unwrap();
+
+        let verdicts = check(day, &case).unwrap();
+        assert_eq!(verdicts.len(), 1);
+        assert!(matches!(verdicts[0], (1, Verdict::Match(ref a)) if a == "3000"));
+    }
+}
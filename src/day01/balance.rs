@@ -0,0 +1,456 @@
+//! Redistributes the elves' calorie items across a number of elves so the heaviest load is
+//! as light as possible: multiway number partitioning, solved exactly for small inputs and
+//! with the greedy and Karmarkar-Karp heuristics for large ones.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::Elf;
+
+/// Inputs with at most this many items are partitioned exactly by [`Method::Auto`].
+pub const EXACT_LIMIT: usize = 20;
+
+/// One calorie item and where it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Item {
+    /// Index of the elf that carried it in the input.
+    pub elf: usize,
+    /// Position in that elf's list.
+    pub index: usize,
+    pub calories: u32,
+}
+
+/// The items one elf carries under a plan.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Load {
+    pub total: u32,
+    pub items: Vec<Item>,
+}
+
+impl Load {
+    fn add(&mut self, item: Item) {
+        self.total += item.calories;
+        self.items.push(item);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Exact for up to [`EXACT_LIMIT`] items, otherwise the better of the two heuristics.
+    Auto,
+    /// Branch and bound; exponential in the number of items.
+    Exact,
+    /// Largest item first onto the lightest load.
+    Greedy,
+    /// Multiway Karmarkar-Karp differencing.
+    KarmarkarKarp,
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Method::Auto),
+            "exact" => Ok(Method::Exact),
+            "greedy" => Ok(Method::Greedy),
+            "kk" => Ok(Method::KarmarkarKarp),
+            _ => Err(format!(
+                "unknown method '{}', expected auto, exact, greedy or kk",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Method::Auto => "auto",
+            Method::Exact => "exact",
+            Method::Greedy => "greedy",
+            Method::KarmarkarKarp => "kk",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// An assignment of every item to one of the elves, heaviest load first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    /// The method that produced the plan; never [`Method::Auto`].
+    pub method: Method,
+    /// How many elves the items were spread over. Elves beyond the last load carry nothing,
+    /// which is how it goes when there are more elves than items.
+    pub elves: usize,
+    pub loads: Vec<Load>,
+    /// No plan can do better than this: the larger of the biggest item and the mean load.
+    pub lower_bound: u32,
+}
+
+impl Plan {
+    fn new(method: Method, mut loads: Vec<Load>, lower_bound: u32) -> Self {
+        for load in &mut loads {
+            load.items
+                .sort_by_key(|i| (Reverse(i.calories), i.elf, i.index));
+        }
+        loads.sort_by_key(|l| Reverse(l.total));
+        Self {
+            method,
+            elves: loads.len(),
+            loads,
+            lower_bound,
+        }
+    }
+
+    /// The heaviest load, which the plan minimizes.
+    pub fn max_load(&self) -> u32 {
+        self.loads.first().map(|l| l.total).unwrap_or(0)
+    }
+
+    pub fn is_optimal(&self) -> bool {
+        self.method == Method::Exact || self.max_load() == self.lower_bound
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} elves, heaviest load {} ({}, lower bound {}{})",
+            self.elves,
+            self.max_load(),
+            self.method,
+            self.lower_bound,
+            if self.is_optimal() { ", optimal" } else { "" }
+        )?;
+        for (n, load) in self.loads.iter().enumerate() {
+            let items: Vec<String> = load
+                .items
+                .iter()
+                .map(|i| format!("{} (elf {} #{})", i.calories, i.elf + 1, i.index + 1))
+                .collect();
+            writeln!(f, "elf {}: {} = {}", n + 1, load.total, items.join(" + "))?;
+        }
+        if self.elves > self.loads.len() {
+            writeln!(
+                f,
+                "the other {} elves carry nothing",
+                self.elves - self.loads.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Every item of every elf, largest first.
+pub fn items(elves: &[Elf]) -> Vec<Item> {
+    let mut items: Vec<Item> = elves
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, e)| {
+            e.calories
+                .iter()
+                .enumerate()
+                .map(move |(index, calories)| Item {
+                    elf,
+                    index,
+                    calories: *calories,
+                })
+        })
+        .collect();
+    items.sort_by_key(|i| (Reverse(i.calories), i.elf, i.index));
+    items
+}
+
+/// Spreads the items of `elves` over `bins` elves using `method`. `None` when `bins` is 0.
+pub fn balance(elves: &[Elf], bins: usize, method: Method) -> Option<Plan> {
+    if bins == 0 {
+        return None;
+    }
+    let requested = bins;
+    let items = items(elves);
+    // Each item fills at most one elf, so the rest would only ever stay empty.
+    let bins = bins.min(items.len()).max(1);
+    let total: u64 = items.iter().map(|i| u64::from(i.calories)).sum();
+    let biggest = items.first().map(|i| i.calories).unwrap_or(0);
+    let mean = u32::try_from(total.div_ceil(requested as u64)).unwrap_or(u32::MAX);
+    let lower_bound = biggest.max(mean);
+
+    let mut plan = match method {
+        Method::Exact => Plan::new(Method::Exact, exact(&items, bins, lower_bound), lower_bound),
+        Method::Greedy => Plan::new(Method::Greedy, greedy(&items, bins), lower_bound),
+        Method::KarmarkarKarp => Plan::new(
+            Method::KarmarkarKarp,
+            karmarkar_karp(&items, bins),
+            lower_bound,
+        ),
+        Method::Auto if items.len() <= EXACT_LIMIT => {
+            return balance(elves, requested, Method::Exact);
+        }
+        Method::Auto => {
+            let greedy = Plan::new(Method::Greedy, greedy(&items, bins), lower_bound);
+            let kk = Plan::new(
+                Method::KarmarkarKarp,
+                karmarkar_karp(&items, bins),
+                lower_bound,
+            );
+            if kk.max_load() < greedy.max_load() {
+                kk
+            } else {
+                greedy
+            }
+        }
+    };
+    plan.elves = requested;
+    Some(plan)
+}
+
+/// Longest processing time first: each item, largest first, goes onto the lightest load.
+fn greedy(items: &[Item], bins: usize) -> Vec<Load> {
+    let mut loads = vec![Load::default(); bins];
+    let mut lightest: BinaryHeap<Reverse<(u32, usize)>> =
+        (0..bins).map(|b| Reverse((0, b))).collect();
+    for item in items {
+        let Reverse((_, bin)) = lightest.pop().unwrap();
+        loads[bin].add(*item);
+        lightest.push(Reverse((loads[bin].total, bin)));
+    }
+    loads
+}
+
+/// Starts with one partial partition per item and repeatedly merges the two whose loads
+/// differ the most, pairing the heaviest load of one with the lightest of the other.
+fn karmarkar_karp(items: &[Item], bins: usize) -> Vec<Load> {
+    let spread = |loads: &[Load]| loads[0].total - loads[loads.len() - 1].total;
+
+    let mut partitions: Vec<Option<Vec<Load>>> = items
+        .iter()
+        .map(|item| {
+            let mut loads = vec![Load::default(); bins];
+            loads[0].add(*item);
+            Some(loads)
+        })
+        .collect();
+    let mut widest: BinaryHeap<(u32, Reverse<usize>)> = partitions
+        .iter()
+        .enumerate()
+        .map(|(id, p)| (spread(p.as_ref().unwrap()), Reverse(id)))
+        .collect();
+
+    while widest.len() > 1 {
+        let (_, Reverse(a)) = widest.pop().unwrap();
+        let (_, Reverse(b)) = widest.pop().unwrap();
+        let a = partitions[a].take().unwrap();
+        let b = partitions[b].take().unwrap();
+
+        let mut merged: Vec<Load> = a
+            .into_iter()
+            .zip(b.into_iter().rev())
+            .map(|(mut heavy, light)| {
+                for item in light.items {
+                    heavy.add(item);
+                }
+                heavy
+            })
+            .collect();
+        merged.sort_by_key(|l| Reverse(l.total));
+        widest.push((spread(&merged), Reverse(partitions.len())));
+        partitions.push(Some(merged));
+    }
+
+    match widest.pop() {
+        Some((_, Reverse(id))) => partitions[id].take().unwrap(),
+        None => vec![Load::default(); bins],
+    }
+}
+
+/// Branch and bound over the items, largest first, starting from the better heuristic
+/// result. Loads that are equal are interchangeable, so only the first of them is tried.
+fn exact(items: &[Item], bins: usize, lower_bound: u32) -> Vec<Load> {
+    struct Search<'a> {
+        items: &'a [Item],
+        lower_bound: u32,
+        totals: Vec<u32>,
+        assignment: Vec<usize>,
+        best: u32,
+        best_assignment: Option<Vec<usize>>,
+    }
+
+    impl Search<'_> {
+        /// Returns true once a plan matching the lower bound is found.
+        fn run(&mut self, next: usize) -> bool {
+            let Some(item) = self.items.get(next) else {
+                let max = self.totals.iter().copied().max().unwrap_or(0);
+                if max < self.best {
+                    self.best = max;
+                    self.best_assignment = Some(self.assignment.clone());
+                }
+                return self.best <= self.lower_bound;
+            };
+
+            for bin in 0..self.totals.len() {
+                let total = self.totals[bin];
+                if self.totals[..bin].contains(&total) || total + item.calories >= self.best {
+                    continue;
+                }
+                self.totals[bin] += item.calories;
+                self.assignment.push(bin);
+                let done = self.run(next + 1);
+                self.assignment.pop();
+                self.totals[bin] -= item.calories;
+                if done {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+
+    let greedy = greedy(items, bins);
+    let kk = karmarkar_karp(items, bins);
+    let start = [greedy, kk]
+        .into_iter()
+        .min_by_key(|loads| loads.iter().map(|l| l.total).max())
+        .unwrap();
+    let mut search = Search {
+        items,
+        lower_bound,
+        totals: vec![0; bins],
+        assignment: vec![],
+        best: start.iter().map(|l| l.total).max().unwrap_or(0),
+        best_assignment: None,
+    };
+    if search.best > lower_bound {
+        search.run(0);
+    }
+
+    match search.best_assignment {
+        Some(assignment) => {
+            let mut loads = vec![Load::default(); bins];
+            for (item, bin) in items.iter().zip(assignment) {
+                loads[bin].add(*item);
+            }
+            loads
+        }
+        None => start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::parse_elves;
+    use crate::generate::{self, Rng};
+
+    fn elves(calories: &[&[u32]]) -> Vec<Elf> {
+        calories.iter().map(|c| Elf::new(c.to_vec())).collect()
+    }
+
+    fn check_complete(elves: &[Elf], plan: &Plan) {
+        let mut placed: Vec<(usize, usize)> = plan
+            .loads
+            .iter()
+            .flat_map(|l| {
+                assert_eq!(l.total, l.items.iter().map(|i| i.calories).sum::<u32>());
+                l.items.iter().map(|i| (i.elf, i.index))
+            })
+            .collect();
+        placed.sort();
+        let mut expected: Vec<(usize, usize)> =
+            items(elves).iter().map(|i| (i.elf, i.index)).collect();
+        expected.sort();
+        assert_eq!(placed, expected);
+    }
+
+    #[test]
+    fn test_exact_beats_the_heuristics() {
+        let elves = elves(&[&[3, 3], &[2, 2, 2]]);
+        for method in [Method::Greedy, Method::KarmarkarKarp] {
+            assert_eq!(balance(&elves, 2, method).unwrap().max_load(), 7);
+        }
+        let plan = balance(&elves, 2, Method::Auto).unwrap();
+        assert_eq!(plan.method, Method::Exact);
+        assert_eq!(plan.max_load(), 6);
+        check_complete(&elves, &plan);
+    }
+
+    #[test]
+    fn test_balance_the_sample() {
+        let (_, elves) = parse_elves(include_str!("./sample.txt")).unwrap();
+        let plan = balance(&elves, 3, Method::Exact).unwrap();
+        assert_eq!(plan.lower_bound, 18334);
+        assert_eq!(plan.max_load(), 19000);
+        assert_eq!(plan.loads.len(), 3);
+        check_complete(&elves, &plan);
+    }
+
+    #[test]
+    fn test_heuristics_against_exact() {
+        let mut rng = Rng::new(23);
+        for _ in 0..20 {
+            let elves: Vec<Elf> = (0..4)
+                .map(|_| {
+                    Elf::new(
+                        (0..rng.between(1, 4))
+                            .map(|_| rng.between(1, 100) as u32)
+                            .collect(),
+                    )
+                })
+                .collect();
+            let optimum = balance(&elves, 3, Method::Exact).unwrap().max_load();
+            for method in [Method::Greedy, Method::KarmarkarKarp] {
+                let plan = balance(&elves, 3, method).unwrap();
+                check_complete(&elves, &plan);
+                // Both heuristics are within 4/3 of the optimum.
+                assert!(plan.max_load() as f64 <= optimum as f64 * 4.0 / 3.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heuristics_on_large_inputs() {
+        let input = generate::day01(&mut Rng::new(23), 500);
+        let (_, elves) = parse_elves(&input).unwrap();
+        let biggest = items(&elves)[0].calories;
+        for method in [Method::Greedy, Method::KarmarkarKarp, Method::Auto] {
+            let plan = balance(&elves, 7, method).unwrap();
+            check_complete(&elves, &plan);
+            if method != Method::KarmarkarKarp {
+                // Greedy only adds an item to a load at or below the average, so no load
+                // ends up more than one item above it.
+                assert!(plan.max_load() <= plan.lower_bound + biggest);
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        let two = elves(&[&[5, 1]]);
+        assert_eq!(balance(&two, 0, Method::Auto), None);
+        let plan = balance(&two, 4, Method::Auto).unwrap();
+        assert_eq!(plan.elves, 4);
+        assert_eq!(plan.loads.len(), 2);
+        assert_eq!(plan.max_load(), 5);
+        assert!(plan.is_optimal());
+        assert!(plan
+            .to_string()
+            .ends_with("the other 2 elves carry nothing\n"));
+
+        let huge = balance(&two, u32::MAX as usize + 1, Method::KarmarkarKarp).unwrap();
+        assert_eq!(huge.loads.len(), 2);
+        assert_eq!(huge.lower_bound, 5);
+        assert_eq!(
+            balance(&[], 2, Method::KarmarkarKarp).unwrap().max_load(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_method_from_str() {
+        assert_eq!("kk".parse(), Ok(Method::KarmarkarKarp));
+        assert!("best".parse::<Method>().is_err());
+    }
+}
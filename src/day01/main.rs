@@ -1,81 +1,142 @@
-use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
 
-use nom::{
-    character::complete::{self, newline},
-    multi::separated_list1,
-    sequence::tuple,
-    IResult,
-};
+use advent_of_code_rust::day01::balance::{self, Method};
+use advent_of_code_rust::day01::stats::{Report, HISTOGRAM_BUCKETS};
+use advent_of_code_rust::day01::table::{self, Format};
+use advent_of_code_rust::day01::{top_k_calories, Day01, ElfReader, Leaders};
+use advent_of_code_rust::error::AocError;
+use advent_of_code_rust::input;
+use advent_of_code_rust::solution::{parse_all, Solution};
 
-fn main() {
-    let input = include_str!("./data.txt");
-    let (_, elves) = parse_elves(input).unwrap();
-    let be = find_max_calories(&elves).unwrap();
-    println!("max calories: {:?}", be.total_calories());
-
-    println!("max 3 calories: {:?}", find_max_3_calories(&elves).unwrap());
-}
-
-fn find_max_calories(elves: &[Elf]) -> Option<&Elf> {
-    elves.iter().max_by(|a, b| a.cmp(b))
+/// Usage: `day01 [--stream | --stats | --balance N [--method M]] [INPUT]`. With `--stream`
+/// the input is read elf by elf and the leaders are printed whenever they change, so the
+/// input never has to fit in memory. `--stats` follows the answers with a statistics report
+/// on the elves, and `--balance` with a plan spreading every item over `N` elves so the
+/// heaviest load is as light as possible (`M` is auto, exact, greedy or kk).
+///
+/// `--export F` prints the parsed inventory and `--ranking F` every elf from most to least
+/// calories instead, as csv or json. An INPUT ending in `.csv` is imported as an inventory.
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn find_max_3_calories(elves: &[Elf]) -> Option<u32> {
-    let mut c = elves.to_vec();
-    c.sort_by(|a, b| b.cmp(a));
+fn run() -> Result<(), AocError> {
+    let mut stream = false;
+    let mut stats = false;
+    let mut balance = None;
+    let mut method = Method::Auto;
+    let mut export = None;
+    let mut ranking = None;
+    let mut path = None;
 
-    let res = c[0..3].iter().map(|e| e.total_calories()).sum();
-    Some(res)
-}
-#[derive(Debug, Clone)]
-struct Elf {
-    calories: Vec<u32>,
-}
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--stream" => stream = true,
+            "--stats" => stats = true,
+            "--balance" => match args.next().and_then(|n| n.parse::<usize>().ok()) {
+                Some(n) if n > 0 => balance = Some(n),
+                _ => usage("--balance needs a number of elves"),
+            },
+            "--method" => match args.next().map(|m| m.parse()) {
+                Some(Ok(m)) => method = m,
+                Some(Err(e)) => usage(&e),
+                None => usage("--method needs a method"),
+            },
+            "--export" => export = Some(format(args.next())),
+            "--ranking" => ranking = Some(format(args.next())),
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.as_deref();
 
-impl Elf {
-    fn parse(s: &str) -> IResult<&str, Self> {
-        let (s, calories) = separated_list1(newline, Elf::parse_calories)(s)?;
-        Ok((s, Elf { calories }))
+    if stream && (stats || balance.is_some() || export.is_some() || ranking.is_some()) {
+        usage("only the answers can be computed with --stream");
+    }
+    if stream {
+        return run_streaming(path);
     }
 
-    fn total_calories(&self) -> u32 {
-        self.calories.iter().sum()
+    let input = input::load(1, path)?;
+    let elves = match path {
+        Some(path) if path.ends_with(".csv") => table::import_csv(&input)?,
+        _ => parse_all::<Day01>(&input)?,
+    };
+    if let Some(format) = export {
+        println!("{}", table::export_inventory(&elves, format)?);
+        return Ok(());
+    }
+    if let Some(format) = ranking {
+        println!("{}", table::export_ranking(&elves, format)?);
+        return Ok(());
     }
 
-    fn parse_calories(s: &str) -> IResult<&str, u32> {
-        complete::u32(s)
+    let top = top_k_calories(&elves, 1);
+    let carrier = top.first().map(|r| elves[r.index].label(r.index));
+    println!(
+        "max calories: {} ({})",
+        Day01::part1(&elves)?,
+        carrier.unwrap_or_default()
+    );
+    println!("max 3 calories: {}", Day01::part2(&elves)?);
+    if stats {
+        if let Some(report) = Report::new(&elves, HISTOGRAM_BUCKETS) {
+            print!("\n{}", report);
+        }
+    }
+    if let Some(plan) = balance.and_then(|n| balance::balance(&elves, n, method)) {
+        print!("\n{}", plan);
     }
+    Ok(())
+}
 
-    pub fn cmp(&self, other: &Self) -> Ordering {
-        self.total_calories().cmp(&other.total_calories())
+fn format(arg: Option<String>) -> Format {
+    match arg.map(|f| f.parse()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => usage(&e),
+        None => usage("--export and --ranking need a format"),
     }
 }
 
-fn parse_elves(s: &str) -> IResult<&str, Vec<Elf>> {
-    let doublenewline = tuple((newline, newline));
-    separated_list1(doublenewline, Elf::parse)(s)
+fn usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2);
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    #[test]
-    fn top_elf() {
-        let example = include_str!("./sample.txt");
-        let (res, elves) = parse_elves(example).unwrap();
-        assert_eq!(res, "");
+fn run_streaming(path: Option<&str>) -> Result<(), AocError> {
+    let reader: Box<dyn BufRead> = match path {
+        Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(BufReader::new(File::open(input::default_path(1))?)),
+    };
 
-        let result = find_max_calories(&elves).unwrap();
-        assert_eq!(result.total_calories(), 24000);
+    let mut leaders = Leaders::new(3);
+    for elf in ElfReader::new(reader) {
+        if leaders.push(&elf?) {
+            let ranked: Vec<String> = leaders
+                .ranked()
+                .iter()
+                .map(|r| format!("{} ({})", leaders.label(r), r.calories))
+                .collect();
+            println!("after {} elves: {}", leaders.seen(), ranked.join(", "));
+        }
     }
 
-    #[test]
-    fn top_3_elves() {
-        let example = include_str!("./sample.txt");
-        let (res, elves) = parse_elves(example).unwrap();
-        assert_eq!(res, "");
-
-        let result = find_max_3_calories(&elves).unwrap();
-        assert_eq!(result, 45000);
-    }
+    let max = leaders
+        .max()
+        .ok_or_else(|| AocError::NoAnswer("no elves in the input".to_string()))?;
+    let total = leaders
+        .total()
+        .ok_or_else(|| AocError::NoAnswer("fewer than 3 elves in the input".to_string()))?;
+    println!("max calories: {} ({})", max.calories, leaders.label(&max));
+    println!("max 3 calories: {}", total);
+    Ok(())
 }
@@ -0,0 +1,277 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::{self, char, newline},
+    combinator::{map, opt},
+    error::context,
+    multi::separated_list1,
+    sequence::{terminated, tuple},
+};
+
+pub mod balance;
+pub mod stats;
+pub mod stream;
+pub mod table;
+
+pub use stats::Report;
+pub use stream::{ElfReader, Leaders};
+
+use aoc_macros::aoc;
+
+use crate::diagnostics::ParseResult;
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+#[aoc(day = 1)]
+impl Solution for Day01 {
+    type Input = Vec<Elf>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        parse_elves(input)
+    }
+
+    fn part1(elves: &Self::Input) -> Result<u32, AocError> {
+        find_max_calories(elves)
+            .map(|e| e.total_calories())
+            .ok_or_else(|| AocError::NoAnswer("no elves in the input".to_string()))
+    }
+
+    fn part2(elves: &Self::Input) -> Result<u32, AocError> {
+        find_max_3_calories(elves)
+            .ok_or_else(|| AocError::NoAnswer("fewer than 3 elves in the input".to_string()))
+    }
+}
+
+pub fn find_max_calories(elves: &[Elf]) -> Option<&Elf> {
+    elves.iter().max_by(|a, b| a.cmp(b))
+}
+
+pub fn find_max_3_calories(elves: &[Elf]) -> Option<u32> {
+    if elves.len() < 3 {
+        return None;
+    }
+
+    let res = top_k_calories(elves, 3).iter().map(|r| r.calories).sum();
+    Some(res)
+}
+
+/// An elf's total calories together with its position in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ranked {
+    pub index: usize,
+    pub calories: u32,
+}
+
+/// Keeps the `k` largest totals seen so far in a min-heap of at most `k` entries, so each
+/// push is O(log k). On ties the elf seen first wins.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<(u32, Reverse<usize>)>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    /// Offers an elf; returns whether it is now among the kept ones.
+    pub fn push(&mut self, index: usize, calories: u32) -> bool {
+        let entry = Reverse((calories, Reverse(index)));
+        if self.heap.len() < self.k {
+            self.heap.push(entry);
+            return true;
+        }
+        if self.heap.peek().is_some_and(|smallest| entry < *smallest) {
+            self.heap.pop();
+            self.heap.push(entry);
+            return true;
+        }
+        false
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// The kept elves, most calories first, leaving them in place.
+    pub fn ranked(&self) -> Vec<Ranked> {
+        self.clone().into_ranked()
+    }
+
+    /// The kept elves, most calories first.
+    pub fn into_ranked(self) -> Vec<Ranked> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((calories, Reverse(index)))| Ranked { index, calories })
+            .collect()
+    }
+}
+
+/// The `k` elves carrying the most calories, most first, in O(n log k). Returns every elf
+/// when there are fewer than `k`.
+pub fn top_k_calories(elves: &[Elf], k: usize) -> Vec<Ranked> {
+    let mut top = TopK::new(k);
+    for (index, elf) in elves.iter().enumerate() {
+        top.push(index, elf.total_calories());
+    }
+    top.into_ranked()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// Given by an optional `name:` line above the calorie counts.
+    pub name: Option<String>,
+    pub calories: Vec<u32>,
+}
+
+impl Elf {
+    pub fn new(calories: Vec<u32>) -> Self {
+        Self {
+            name: None,
+            calories,
+        }
+    }
+
+    pub fn named(name: &str, calories: Vec<u32>) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            calories,
+        }
+    }
+
+    pub fn parse(s: &str) -> ParseResult<'_, Self> {
+        let (s, name) = opt(terminated(Elf::parse_name, newline))(s)?;
+        let (s, calories) = separated_list1(newline, Elf::parse_calories)(s)?;
+        let name = name.map(str::to_string);
+        Ok((s, Elf { name, calories }))
+    }
+
+    /// A `name:` header line, without the colon.
+    pub fn parse_name(s: &str) -> ParseResult<'_, &str> {
+        let name = take_till1(|c| c == ':' || c == '\n');
+        context(
+            "expected an elf name followed by ':'",
+            map(terminated(name, char(':')), str::trim),
+        )(s)
+    }
+
+    /// The elf's name, or `elf N` for the elf at `index` in the input.
+    pub fn label(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("elf {}", index + 1),
+        }
+    }
+
+    pub fn total_calories(&self) -> u32 {
+        self.calories.iter().sum()
+    }
+
+    pub fn parse_calories(s: &str) -> ParseResult<'_, u32> {
+        context("expected a calorie count", complete::u32)(s)
+    }
+
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_calories().cmp(&other.total_calories())
+    }
+}
+
+pub fn parse_elves(s: &str) -> ParseResult<'_, Vec<Elf>> {
+    let doublenewline = tuple((newline, newline));
+    separated_list1(doublenewline, Elf::parse)(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn top_elf() {
+        let example = include_str!("./sample.txt");
+        let (res, elves) = parse_elves(example).unwrap();
+        assert_eq!(res, "");
+
+        let result = find_max_calories(&elves).unwrap();
+        assert_eq!(result.total_calories(), 24000);
+    }
+
+    #[test]
+    fn top_3_elves() {
+        let example = include_str!("./sample.txt");
+        let (res, elves) = parse_elves(example).unwrap();
+        assert_eq!(res, "");
+
+        let result = find_max_3_calories(&elves).unwrap();
+        assert_eq!(result, 45000);
+    }
+
+    #[test]
+    fn top_k_elves() {
+        let example = include_str!("./sample.txt");
+        let (_, elves) = parse_elves(example).unwrap();
+
+        assert_eq!(
+            top_k_calories(&elves, 2),
+            [
+                Ranked {
+                    index: 3,
+                    calories: 24000
+                },
+                Ranked {
+                    index: 2,
+                    calories: 11000
+                },
+            ]
+        );
+        assert_eq!(top_k_calories(&elves, 10).len(), 5);
+        assert!(top_k_calories(&elves, 0).is_empty());
+    }
+
+    #[test]
+    fn top_k_ties_keep_the_first_elf() {
+        let (_, elves) = parse_elves("5\n\n7\n\n5\n\n5").unwrap();
+        let indices: Vec<_> = top_k_calories(&elves, 3).iter().map(|r| r.index).collect();
+        assert_eq!(indices, [1, 0, 2]);
+    }
+
+    #[test]
+    fn named_elves() {
+        let (res, elves) = parse_elves("Alice:\n1000\n2000\n\n3000\n\nBob Smith:\n500").unwrap();
+        assert_eq!(res, "");
+        assert_eq!(
+            elves,
+            [
+                Elf::named("Alice", vec![1000, 2000]),
+                Elf::new(vec![3000]),
+                Elf::named("Bob Smith", vec![500]),
+            ]
+        );
+        assert_eq!(elves[1].label(1), "elf 2");
+        assert_eq!(elves[2].label(2), "Bob Smith");
+    }
+
+    #[test]
+    fn top_3_elves_needs_3_elves() {
+        let (_, elves) = parse_elves("1000\n\n2000").unwrap();
+        assert_eq!(find_max_3_calories(&elves), None);
+        assert!(matches!(Day01::part2(&elves), Err(AocError::NoAnswer(_))));
+    }
+}
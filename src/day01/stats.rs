@@ -0,0 +1,243 @@
+//! Summary statistics over the elves' inventories, printed by `day01 --stats`.
+
+use std::fmt;
+
+use super::Elf;
+
+/// Percentiles listed in the report.
+pub const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+/// Buckets in the histogram printed by `day01 --stats`.
+pub const HISTOGRAM_BUCKETS: usize = 10;
+
+/// Widest histogram bar, in characters.
+const BAR_WIDTH: usize = 40;
+
+/// Count, range, mean, median and standard deviation of a set of values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation.
+    pub std_dev: f64,
+}
+
+impl Summary {
+    /// `None` when there are no values.
+    pub fn of(values: &[u32]) -> Option<Self> {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let count = sorted.len();
+        let mean = sorted.iter().map(|v| *v as f64).sum::<f64>() / count as f64;
+        let variance = sorted
+            .iter()
+            .map(|v| (*v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Some(Self {
+            count,
+            min: *sorted.first()?,
+            max: *sorted.last()?,
+            mean,
+            median: percentile(&sorted, 50.0)?,
+            std_dev: variance.sqrt(),
+        })
+    }
+}
+
+/// The `p`th percentile of `sorted`, interpolating linearly between the closest ranks.
+pub fn percentile(sorted: &[u32], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = p.clamp(0.0, 100.0) / 100.0 * last as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - lo as f64;
+    Some(sorted[lo] as f64 + (sorted[hi] as f64 - sorted[lo] as f64) * weight)
+}
+
+/// Elves whose total falls in `start..=end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bucket {
+    pub start: u32,
+    pub end: u32,
+    pub count: usize,
+}
+
+/// Splits `min..=max` of `values` into `buckets` equally wide buckets (fewer when the range
+/// is narrower than that) and counts the values in each.
+pub fn histogram(values: &[u32], buckets: usize) -> Vec<Bucket> {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return vec![];
+    };
+    let span = (max - min) as u64 + 1;
+    let width = span.div_ceil(buckets.max(1) as u64);
+
+    let mut histogram: Vec<Bucket> = (0..span.div_ceil(width))
+        .map(|i| {
+            let start = *min as u64 + i * width;
+            Bucket {
+                start: start as u32,
+                end: (start + width - 1).min(*max as u64) as u32,
+                count: 0,
+            }
+        })
+        .collect();
+    for value in values {
+        histogram[((*value - min) as u64 / width) as usize].count += 1;
+    }
+    histogram
+}
+
+/// Everything `day01 --stats` prints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub totals: Summary,
+    pub percentiles: Vec<(u8, f64)>,
+    pub items: Summary,
+    /// Each elf's label and how many items it carries, in input order.
+    pub item_counts: Vec<(String, usize)>,
+    pub histogram: Vec<Bucket>,
+}
+
+impl Report {
+    /// `None` when there are no elves.
+    pub fn new(elves: &[Elf], buckets: usize) -> Option<Self> {
+        let mut totals: Vec<u32> = elves.iter().map(|e| e.total_calories()).collect();
+        totals.sort_unstable();
+        let items: Vec<u32> = elves.iter().map(|e| e.calories.len() as u32).collect();
+
+        let item_counts = elves
+            .iter()
+            .enumerate()
+            .map(|(i, elf)| (elf.label(i), elf.calories.len()))
+            .collect();
+
+        Some(Self {
+            totals: Summary::of(&totals)?,
+            percentiles: PERCENTILES
+                .iter()
+                .map(|p| Some((*p, percentile(&totals, *p as f64)?)))
+                .collect::<Option<_>>()?,
+            items: Summary::of(&items)?,
+            item_counts,
+            histogram: histogram(&totals, buckets),
+        })
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let t = &self.totals;
+        writeln!(f, "elves: {}", t.count)?;
+        writeln!(f, "total calories: min {}, max {}", t.min, t.max)?;
+        writeln!(
+            f,
+            "mean {:.1}, median {:.1}, std dev {:.1}",
+            t.mean, t.median, t.std_dev
+        )?;
+        let percentiles: Vec<String> = self
+            .percentiles
+            .iter()
+            .map(|(p, v)| format!("p{} {:.1}", p, v))
+            .collect();
+        writeln!(f, "percentiles: {}", percentiles.join(", "))?;
+
+        let i = &self.items;
+        writeln!(
+            f,
+            "\nitems per elf: min {}, max {}, mean {:.1}, median {:.1}",
+            i.min, i.max, i.mean, i.median
+        )?;
+        let label = self.item_counts.iter().map(|(l, _)| l.len()).max();
+        for (elf, items) in &self.item_counts {
+            writeln!(f, "{:<w$} {:>3} items", elf, items, w = label.unwrap_or(0))?;
+        }
+
+        writeln!(f, "\ntotal calories histogram:")?;
+        let widest = self.histogram.iter().map(|b| b.count).max().unwrap_or(0);
+        let label = self.totals.max.to_string().len();
+        for bucket in &self.histogram {
+            let bar = (bucket.count * BAR_WIDTH).div_ceil(widest.max(1));
+            writeln!(
+                f,
+                "{:>w$}-{:<w$} | {} {}",
+                bucket.start,
+                bucket.end,
+                "#".repeat(bar),
+                bucket.count,
+                w = label
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::parse_elves;
+
+    #[test]
+    fn test_summary_of_the_sample() {
+        let (_, elves) = parse_elves(include_str!("./sample.txt")).unwrap();
+        let report = Report::new(&elves, 4).unwrap();
+
+        assert_eq!(report.totals.count, 5);
+        assert_eq!((report.totals.min, report.totals.max), (4000, 24000));
+        assert_eq!(report.totals.mean, 11000.0);
+        assert_eq!(report.totals.median, 10000.0);
+        assert!((report.totals.std_dev - 6985.7).abs() < 0.1);
+        assert_eq!(report.percentiles[1], (25, 6000.0));
+        let items: Vec<_> = report.item_counts.iter().map(|(_, n)| *n).collect();
+        assert_eq!(items, [3, 1, 2, 3, 1]);
+        assert_eq!(report.item_counts[3].0, "elf 4");
+    }
+
+    #[test]
+    fn test_percentile_interpolates() {
+        assert_eq!(percentile(&[10, 20, 30, 40], 50.0), Some(25.0));
+        assert_eq!(percentile(&[10, 20, 30, 40], 100.0), Some(40.0));
+        assert_eq!(percentile(&[7], 90.0), Some(7.0));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+
+    #[test]
+    fn test_histogram_covers_every_value() {
+        assert_eq!(
+            histogram(&[4000, 6000, 10000, 11000, 24000], 4),
+            [
+                Bucket {
+                    start: 4000,
+                    end: 9000,
+                    count: 2
+                },
+                Bucket {
+                    start: 9001,
+                    end: 14001,
+                    count: 2
+                },
+                Bucket {
+                    start: 14002,
+                    end: 19002,
+                    count: 0
+                },
+                Bucket {
+                    start: 19003,
+                    end: 24000,
+                    count: 1
+                },
+            ]
+        );
+        // A range narrower than the bucket count gets one bucket per value.
+        assert_eq!(histogram(&[5, 5, 6], 10).len(), 2);
+        assert!(histogram(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_no_elves_no_report() {
+        assert_eq!(Report::new(&[], 10), None);
+    }
+}
@@ -0,0 +1,348 @@
+//! Reads elves one at a time from a `BufRead`, so calorie logs far larger than memory can
+//! be ranked, and leaders reported as they change rather than once the whole file is in.
+
+use std::collections::BTreeMap;
+use std::io::BufRead;
+
+use super::{Day01, Elf, Ranked, TopK};
+use crate::error::AocError;
+use crate::solution::parse_all;
+
+/// Yields each elf once the line starting the next one (or the end of input) is read. Each
+/// elf is checked by the same parser as the whole input, so a stream is rejected exactly
+/// where `parse_all::<Day01>` would reject the file. Lines are numbered from the start of
+/// the stream, so a parse error points at the right place in the file.
+pub struct ElfReader<R> {
+    reader: R,
+    buf: String,
+    offset: usize,
+    line: usize,
+    /// The first line of the next elf, read while looking for the end of the current one.
+    next: Option<Line>,
+    /// Whether an elf was read already; errors after the first elf are trailing input.
+    seen: bool,
+    done: bool,
+}
+
+/// One line of the stream, without its line ending.
+struct Line {
+    text: String,
+    number: usize,
+    /// Byte offset of the text in the stream.
+    offset: usize,
+}
+
+impl<R: BufRead> ElfReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: String::new(),
+            offset: 0,
+            line: 0,
+            next: None,
+            seen: false,
+            done: false,
+        }
+    }
+
+    /// The next line, or `None` at end of input.
+    fn next_line(&mut self) -> Result<Option<Line>, AocError> {
+        self.buf.clear();
+        let read = self.reader.read_line(&mut self.buf)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        let mut offset = self.offset;
+        self.offset += read;
+
+        let mut text = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+        text = text.strip_suffix('\r').unwrap_or(text);
+        if self.line == 1 {
+            if let Some(rest) = text.strip_prefix('\u{feff}') {
+                offset += text.len() - rest.len();
+                text = rest;
+            }
+        }
+        Ok(Some(Line {
+            text: text.to_string(),
+            number: self.line,
+            offset,
+        }))
+    }
+
+    fn read_elf(&mut self) -> Result<Option<Elf>, AocError> {
+        // The elf's lines and the blank lines after it, up to the first line of the next elf.
+        let mut lines: Vec<Line> = self.next.take().into_iter().collect();
+        let mut content = !lines.is_empty();
+        let mut ended = false;
+        let at_end = loop {
+            let Some(line) = self.next_line()? else {
+                break true;
+            };
+            let blank = line.text.trim().is_empty();
+            if ended && !blank {
+                self.next = Some(line);
+                break false;
+            }
+            ended |= blank && content;
+            content |= !blank;
+            lines.push(line);
+        };
+        if at_end && self.seen && !content {
+            return Ok(None);
+        }
+
+        // Stand-in elves take the place of the ones before and after, so the parser sees
+        // this elf as it would in the whole input.
+        let prefix = if self.seen { "0\n\n" } else { "" };
+        let body: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        let body = body.join("\n");
+        let text = if at_end {
+            format!("{}{}", prefix, body.trim_end())
+        } else {
+            format!("{}{}\n0", prefix, body)
+        };
+
+        let elves = parse_all::<Day01>(&text)
+            .map_err(|e| relocate(e, &text, prefix.matches('\n').count(), &lines))?;
+        let elf = elves.into_iter().nth(usize::from(self.seen));
+        self.seen = true;
+        Ok(elf)
+    }
+}
+
+/// Moves an error found in the text built from `lines` to where those lines are in the stream.
+fn relocate(mut err: AocError, text: &str, prefix_lines: usize, lines: &[Line]) -> AocError {
+    if let AocError::Parse(d) | AocError::TrailingInput(d) = &mut err {
+        let index = (d.line - 1).saturating_sub(prefix_lines);
+        if let Some(line) = lines.get(index.min(lines.len().saturating_sub(1))) {
+            let line_start = text[..d.offset].rfind('\n').map_or(0, |i| i + 1);
+            d.offset = line.offset + d.offset - line_start;
+            d.line = line.number;
+        }
+    }
+    err
+}
+
+impl<R: BufRead> Iterator for ElfReader<R> {
+    type Item = Result<Elf, AocError>;
+
+    /// Stops after the first error.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.read_elf().transpose();
+        if !matches!(next, Some(Ok(_))) {
+            self.done = true;
+        }
+        next
+    }
+}
+
+/// Running totals over a stream of elves: how many were seen and the `k` carrying the most.
+#[derive(Debug, Clone)]
+pub struct Leaders {
+    seen: usize,
+    top: TopK,
+    /// Names of the current leaders that have one.
+    names: BTreeMap<usize, String>,
+}
+
+impl Leaders {
+    pub fn new(k: usize) -> Self {
+        Self {
+            seen: 0,
+            top: TopK::new(k),
+            names: BTreeMap::new(),
+        }
+    }
+
+    /// Counts the next elf in the stream and reports whether it joined the leaders.
+    pub fn push(&mut self, elf: &Elf) -> bool {
+        let index = self.seen;
+        self.seen += 1;
+        if !self.top.push(index, elf.total_calories()) {
+            return false;
+        }
+
+        if let Some(name) = &elf.name {
+            self.names.insert(index, name.clone());
+        }
+        if self.names.len() > self.top.len() {
+            let kept: Vec<usize> = self.ranked().iter().map(|r| r.index).collect();
+            self.names.retain(|index, _| kept.contains(index));
+        }
+        true
+    }
+
+    /// A leader's name, or `elf N` if it has none.
+    pub fn label(&self, leader: &Ranked) -> String {
+        match self.names.get(&leader.index) {
+            Some(name) => name.clone(),
+            None => format!("elf {}", leader.index + 1),
+        }
+    }
+
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// The elf carrying the most calories so far.
+    pub fn max(&self) -> Option<Ranked> {
+        self.top.ranked().first().copied()
+    }
+
+    /// The current leaders, most calories first.
+    pub fn ranked(&self) -> Vec<Ranked> {
+        self.top.ranked()
+    }
+
+    /// Combined calories of the leaders, once there are `k` of them.
+    pub fn total(&self) -> Option<u32> {
+        if self.top.len() < self.top.k() {
+            return None;
+        }
+        Some(self.ranked().iter().map(|r| r.calories).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::day01::{parse_elves, top_k_calories};
+    use crate::diagnostics::Diagnostic;
+    use crate::generate::{self, Rng};
+
+    #[test]
+    fn test_reads_the_sample() {
+        let example = include_str!("./sample.txt");
+        let elves = ElfReader::new(Cursor::new(example))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let totals: Vec<_> = elves.iter().map(|e| e.total_calories()).collect();
+        assert_eq!(totals, [6000, 4000, 11000, 24000, 10000]);
+    }
+
+    #[test]
+    fn test_bom_and_crlf() {
+        let input = "\u{feff}1000\r\n2000\r\n\r\n3000\r\n\r\n";
+        let totals: Vec<_> = ElfReader::new(Cursor::new(input))
+            .map(|e| e.unwrap().total_calories())
+            .collect();
+        assert_eq!(totals, [3000, 3000]);
+    }
+
+    fn diagnostic(err: AocError) -> (bool, Diagnostic) {
+        match err {
+            AocError::Parse(d) => (false, d),
+            AocError::TrailingInput(d) => (true, d),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_matches_the_batch_parser() {
+        let inputs = [
+            "1000\n\n\n2000",
+            "1000\n  \n2000",
+            "\n1000\n\n2000",
+            "1000\n2x00\n\n3000",
+            "1000\n\nAlice:\n\n3000",
+            "1000 \n\n2000",
+            "",
+        ];
+        for input in inputs {
+            let batch = parse_all::<Day01>(&crate::input::normalize(input)).unwrap_err();
+            let stream = ElfReader::new(Cursor::new(input))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_err();
+            let (batch, stream) = (diagnostic(batch), diagnostic(stream));
+            assert_eq!(stream, batch, "{:?}", input);
+        }
+
+        let input = "Alice:\r\n1000\r\n\r\n2000 \r\n\r\n  \r\n";
+        let batch = parse_all::<Day01>(&crate::input::normalize(input)).unwrap();
+        let stream = ElfReader::new(Cursor::new(input))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(stream, batch);
+    }
+
+    #[test]
+    fn test_error_points_into_the_stream() {
+        let mut reader = ElfReader::new(Cursor::new("1000\n\n2000\n2x00\n\n3000\n"));
+        assert_eq!(reader.next().unwrap().unwrap().total_calories(), 1000);
+        match reader.next() {
+            Some(Err(AocError::TrailingInput(diagnostic))) => {
+                assert_eq!((diagnostic.line, diagnostic.column), (4, 2));
+                assert_eq!(diagnostic.offset, 12);
+                assert_eq!(diagnostic.source_line, "2x00");
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_named_elves() {
+        let input = "Alice:\n1000\n\n2000\n\nBob:\n\n3000\n";
+        let mut reader = ElfReader::new(Cursor::new(input));
+        assert_eq!(
+            reader.next().unwrap().unwrap(),
+            Elf::named("Alice", vec![1000])
+        );
+        assert_eq!(reader.next().unwrap().unwrap(), Elf::new(vec![2000]));
+        assert!(matches!(reader.next(), Some(Err(_))));
+
+        let mut leaders = Leaders::new(1);
+        leaders.push(&Elf::named("Alice", vec![1000]));
+        leaders.push(&Elf::new(vec![2000]));
+        leaders.push(&Elf::named("Bob", vec![1500]));
+        assert_eq!(leaders.label(&leaders.max().unwrap()), "elf 2");
+        leaders.push(&Elf::named("Cy", vec![2500]));
+        assert_eq!(leaders.label(&leaders.max().unwrap()), "Cy");
+    }
+
+    #[test]
+    fn test_named_elf_needs_calories() {
+        let input = "Alice:\n1000\n\nBob:\n\n3000\n";
+        let mut reader = ElfReader::new(Cursor::new(input));
+        reader.next();
+        let (_, diagnostic) = diagnostic(reader.next().unwrap().unwrap_err());
+        assert_eq!(diagnostic.line, 5);
+    }
+
+    #[test]
+    fn test_leaders_match_top_k() {
+        let input = generate::day01(&mut Rng::new(21), 2_000);
+        let (_, elves) = parse_elves(&input).unwrap();
+
+        let mut leaders = Leaders::new(3);
+        let mut changes = 0;
+        for elf in ElfReader::new(Cursor::new(&input)) {
+            if leaders.push(&elf.unwrap()) {
+                changes += 1;
+            }
+        }
+
+        assert_eq!(leaders.seen(), 2_000);
+        assert_eq!(leaders.ranked(), top_k_calories(&elves, 3));
+        assert_eq!(leaders.max(), top_k_calories(&elves, 1).first().copied());
+        assert!((3..2_000).contains(&changes));
+    }
+
+    #[test]
+    fn test_total_needs_k_elves() {
+        let mut leaders = Leaders::new(3);
+        assert_eq!(leaders.max(), None);
+        leaders.push(&Elf::new(vec![1, 2]));
+        leaders.push(&Elf::new(vec![4]));
+        assert_eq!(leaders.total(), None);
+        leaders.push(&Elf::new(vec![5]));
+        assert_eq!(leaders.total(), Some(12));
+    }
+}
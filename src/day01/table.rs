@@ -0,0 +1,359 @@
+//! Spreadsheet-friendly views of the elves: the inventory and the ranking as CSV or JSON,
+//! and the inventory read back from CSV.
+//!
+//! The inventory has one row per calorie item, grouped into elves by the `elf` column:
+//!
+//! ```text
+//! elf,name,calories
+//! 1,Alice,1000
+//! 1,Alice,2000
+//! 2,,3000
+//! ```
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::{top_k_calories, Elf};
+use crate::diagnostics::Diagnostic;
+use crate::error::AocError;
+
+pub const INVENTORY_HEADER: &str = "elf,name,calories";
+pub const RANKING_HEADER: &str = "rank,elf,name,calories";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{}', expected csv or json", s)),
+        }
+    }
+}
+
+/// One elf as exported to JSON; `elf` counts from 1.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ElfRecord {
+    pub elf: usize,
+    pub name: Option<String>,
+    pub calories: Vec<u32>,
+    pub total: u32,
+}
+
+/// One line of the ranking, most calories first; `rank` and `elf` count from 1.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankRecord {
+    pub rank: usize,
+    pub elf: usize,
+    pub name: Option<String>,
+    pub calories: u32,
+}
+
+pub fn records(elves: &[Elf]) -> Vec<ElfRecord> {
+    elves
+        .iter()
+        .enumerate()
+        .map(|(index, elf)| ElfRecord {
+            elf: index + 1,
+            name: elf.name.clone(),
+            calories: elf.calories.clone(),
+            total: elf.total_calories(),
+        })
+        .collect()
+}
+
+/// Every elf, ranked like [`top_k_calories`]: most calories first, ties in input order.
+pub fn ranking(elves: &[Elf]) -> Vec<RankRecord> {
+    top_k_calories(elves, elves.len())
+        .into_iter()
+        .enumerate()
+        .map(|(rank, r)| RankRecord {
+            rank: rank + 1,
+            elf: r.index + 1,
+            name: elves[r.index].name.clone(),
+            calories: r.calories,
+        })
+        .collect()
+}
+
+pub fn export_inventory(elves: &[Elf], format: Format) -> Result<String, AocError> {
+    let records = records(elves);
+    match format {
+        Format::Json => Ok(serde_json::to_string_pretty(&records)?),
+        Format::Csv => {
+            let mut lines = vec![INVENTORY_HEADER.to_string()];
+            for record in &records {
+                let name = field(record.name.as_deref().unwrap_or(""));
+                for calories in &record.calories {
+                    lines.push(format!("{},{},{}", record.elf, name, calories));
+                }
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+pub fn export_ranking(elves: &[Elf], format: Format) -> Result<String, AocError> {
+    let ranking = ranking(elves);
+    match format {
+        Format::Json => Ok(serde_json::to_string_pretty(&ranking)?),
+        Format::Csv => {
+            let mut lines = vec![RANKING_HEADER.to_string()];
+            lines.extend(ranking.iter().map(|r| {
+                format!(
+                    "{},{},{},{}",
+                    r.rank,
+                    r.elf,
+                    field(r.name.as_deref().unwrap_or("")),
+                    r.calories
+                )
+            }));
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+/// Reads an inventory exported by [`export_inventory`] or written by hand in a spreadsheet.
+/// Columns are found by header, so they may come in any order and extra ones are ignored;
+/// `name` is optional. Rows with the same `elf` value belong to one elf, and elves keep the
+/// order in which they first appear.
+pub fn import_csv(input: &str) -> Result<Vec<Elf>, AocError> {
+    let records = split_records(input).map_err(|(at, e)| error(input, at, e))?;
+    let Some((header, rows)) = records.split_first() else {
+        return Ok(vec![]);
+    };
+    let header: Vec<String> = header
+        .fields
+        .iter()
+        .map(|(_, h)| h.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (Some(elf_col), Some(calories_col)) = (column("elf"), column("calories")) else {
+        return Err(error(
+            input,
+            0,
+            "expected a header with 'elf' and 'calories' columns",
+        ));
+    };
+    let name_col = column("name");
+
+    let mut ids: HashMap<&str, usize> = HashMap::new();
+    let mut elves: Vec<Elf> = vec![];
+    for row in rows {
+        let get = |col: usize| {
+            row.fields
+                .get(col)
+                .map(|(at, f)| (*at, f.trim()))
+                .ok_or_else(|| error(input, row.end, "expected more columns"))
+        };
+
+        let (_, id) = get(elf_col)?;
+        let (at, calories) = get(calories_col)?;
+        let calories = calories
+            .parse::<u32>()
+            .map_err(|_| error(input, at, "expected a calorie count"))?;
+        let name = match name_col {
+            Some(col) => Some(get(col)?.1).filter(|n| !n.is_empty()),
+            None => None,
+        };
+
+        let index = *ids.entry(id).or_insert_with(|| {
+            elves.push(Elf::new(vec![]));
+            elves.len() - 1
+        });
+        let elf = &mut elves[index];
+        elf.calories.push(calories);
+        if elf.name.is_none() {
+            elf.name = name.map(str::to_string);
+        }
+    }
+    Ok(elves)
+}
+
+/// One CSV record: its fields with their offsets in the input, and where the record ends.
+struct Record {
+    fields: Vec<(usize, String)>,
+    end: usize,
+}
+
+/// Splits CSV text into records, skipping blank lines. Fields may be quoted, with `""`
+/// standing for a quote inside them, and quoted fields may span lines.
+fn split_records(input: &str) -> Result<Vec<Record>, (usize, &'static str)> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut chars = input.char_indices().peekable();
+    loop {
+        let start = chars.peek().map_or(input.len(), |(i, _)| *i);
+        let mut value = String::new();
+        // Where the record ends, unless a comma ended just the field.
+        let mut end = None;
+        if chars.next_if(|(_, c)| *c == '"').is_some() {
+            loop {
+                match chars.next() {
+                    Some((_, '"')) if chars.next_if(|(_, c)| *c == '"').is_some() => {
+                        value.push('"')
+                    }
+                    Some((_, '"')) => break,
+                    Some((_, c)) => value.push(c),
+                    None => return Err((start, "expected a closing quote")),
+                }
+            }
+            match chars.next() {
+                None => end = Some(input.len()),
+                Some((_, ',')) => {}
+                Some((i, '\n')) => end = Some(i),
+                Some((i, '\r')) if chars.next_if(|(_, c)| *c == '\n').is_some() => end = Some(i),
+                Some((i, _)) => return Err((i, "expected ',' after a quoted field")),
+            }
+        } else {
+            end = Some(input.len());
+            for (i, c) in chars.by_ref() {
+                match c {
+                    ',' => {
+                        end = None;
+                        break;
+                    }
+                    '\n' => {
+                        end = Some(i);
+                        break;
+                    }
+                    c => value.push(c),
+                }
+            }
+            if end.is_some() && value.ends_with('\r') {
+                value.pop();
+                end = end.map(|e| e - 1);
+            }
+        }
+        fields.push((start, value));
+
+        let Some(end) = end else { continue };
+        let blank = fields.len() == 1 && fields[0].1.trim().is_empty();
+        let fields = std::mem::take(&mut fields);
+        if !blank {
+            records.push(Record { fields, end });
+        }
+        if chars.peek().is_none() {
+            return Ok(records);
+        }
+    }
+}
+
+/// Quotes a field when it holds a comma, quote or line break.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn error(input: &str, offset: usize, message: &str) -> AocError {
+    AocError::Parse(Diagnostic::at(input, offset, Some(message.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::{find_max_calories, parse_elves};
+
+    #[test]
+    fn test_csv_round_trip() {
+        let (_, mut elves) = parse_elves(include_str!("./sample.txt")).unwrap();
+        elves[0].name = Some("Smith, \"Al\"".to_string());
+        elves[3].name = Some("Bea".to_string());
+
+        let csv = export_inventory(&elves, Format::Csv).unwrap();
+        assert!(csv.starts_with("elf,name,calories\n1,\"Smith, \"\"Al\"\"\",1000\n"));
+        assert_eq!(import_csv(&csv).unwrap(), elves);
+    }
+
+    #[test]
+    fn test_names_with_line_breaks_round_trip() {
+        let elves = vec![
+            Elf::named("two\nlines", vec![1000]),
+            Elf::named("crlf \"quoted\"\r\nname", vec![2000, 3000]),
+            Elf::new(vec![4000]),
+        ];
+        let csv = export_inventory(&elves, Format::Csv).unwrap();
+        assert!(csv.contains("1,\"two\nlines\",1000\n"));
+        assert_eq!(import_csv(&csv).unwrap(), elves);
+    }
+
+    #[test]
+    fn test_import_from_a_spreadsheet() {
+        let csv = "Calories,Notes,Elf\r\n1000,,a\r\n500,x,b\r\n2000,,a\r\n\r\n";
+        let elves = import_csv(csv).unwrap();
+        assert_eq!(elves, [Elf::new(vec![1000, 2000]), Elf::new(vec![500])]);
+        assert_eq!(find_max_calories(&elves).unwrap().total_calories(), 3000);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        let err = import_csv("elf,calories\n1,1000\n1,lots\n").unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::Parse(Diagnostic {
+                line: 3,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(import_csv("elf,name\n1,Al\n").is_err());
+        assert!(import_csv("elf,calories\n1\n").is_err());
+        assert!(import_csv("elf,calories\n\"1,1000\n").is_err());
+    }
+
+    #[test]
+    fn test_ranking() {
+        let elves = vec![
+            Elf::named("Al", vec![5]),
+            Elf::new(vec![7]),
+            Elf::new(vec![5]),
+        ];
+        assert_eq!(
+            export_ranking(&elves, Format::Csv).unwrap(),
+            "rank,elf,name,calories\n1,2,,7\n2,1,Al,5\n3,3,,5"
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&export_ranking(&elves, Format::Json).unwrap()).unwrap();
+        assert_eq!(json[1]["name"], "Al");
+        assert_eq!(json[0]["elf"], 2);
+    }
+
+    #[test]
+    fn test_split_records() {
+        let fields = |input| {
+            split_records(input)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.fields.into_iter().map(|(_, f)| f).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(fields("a,\"b,c\",d"), [vec!["a", "b,c", "d"]]);
+        assert_eq!(
+            fields("a,\r\n\r\n\"b\nc\"\r\n"),
+            [vec!["a", ""], vec!["b\nc"]]
+        );
+        assert!(fields("").is_empty());
+        assert_eq!(
+            split_records("x\n\"a\"b").err(),
+            Some((5, "expected ',' after a quoted field"))
+        );
+        let ends: Vec<_> = split_records("a,b\r\nc")
+            .unwrap()
+            .iter()
+            .map(|r| r.end)
+            .collect();
+        assert_eq!(ends, [3, 6]);
+    }
+}
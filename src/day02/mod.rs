@@ -0,0 +1,211 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{anychar, newline},
+    error::context,
+    multi::separated_list1,
+    sequence::separated_pair,
+};
+
+pub mod rules;
+
+pub use rules::{Ruleset, Shape};
+
+use aoc_macros::aoc;
+
+use crate::diagnostics::{expected, ParseResult};
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day02;
+
+/// The strategy guide read both ways: second column as a shape, and as an outcome.
+pub struct StrategyGuide {
+    pub games: Vec<(PlayChoice, PlayChoice)>,
+    pub games_v2: Vec<(PlayChoice, PlayChoice)>,
+}
+
+#[aoc(day = 2)]
+impl Solution for Day02 {
+    type Input = StrategyGuide;
+    type Output1 = i32;
+    type Output2 = i32;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        let (_, games_v2) = parse_games_v2(input)?;
+        let (input, games) = parse_games(input)?;
+        Ok((input, StrategyGuide { games, games_v2 }))
+    }
+
+    fn part1(guide: &Self::Input) -> Result<i32, AocError> {
+        Ok(total_score(&guide.games))
+    }
+
+    fn part2(guide: &Self::Input) -> Result<i32, AocError> {
+        Ok(total_score(&guide.games_v2))
+    }
+}
+
+pub fn total_score(games: &[(PlayChoice, PlayChoice)]) -> i32 {
+    games.iter().map(|(a, b)| b.game_score(a)).sum::<i32>()
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PlayChoice {
+    Rock = 1,
+    Paper = 2,
+    Scissors = 3,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GameOutcome {
+    Lose = 0,
+    Draw = 3,
+    Win = 6,
+}
+
+impl GameOutcome {
+    pub fn score(&self) -> i32 {
+        *self as i32
+    }
+
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        let (rest, c) = anychar(input)?;
+        match c {
+            'X' => Ok((rest, GameOutcome::Lose)),
+            'Y' => Ok((rest, GameOutcome::Draw)),
+            'Z' => Ok((rest, GameOutcome::Win)),
+            _ => Err(expected(input, "expected 'X', 'Y' or 'Z'")),
+        }
+    }
+}
+
+pub fn parse_games(input: &str) -> ParseResult<'_, Vec<(PlayChoice, PlayChoice)>> {
+    separated_list1(newline, PlayChoice::parse_pair)(input)
+}
+
+pub fn parse_games_v2(input: &str) -> ParseResult<'_, Vec<(PlayChoice, PlayChoice)>> {
+    separated_list1(newline, PlayChoice::parse_pair_v2)(input)
+}
+
+impl PlayChoice {
+    pub fn game_score(&self, other: &Self) -> i32 {
+        self.game(other).score() + self.score()
+    }
+
+    pub fn parse_pair(input: &str) -> ParseResult<'_, (Self, Self)> {
+        separated_pair(
+            Self::parse,
+            context("expected ' '", tag(" ")),
+            Self::parse_second,
+        )(input)
+    }
+
+    pub fn parse_pair_v2(input: &str) -> ParseResult<'_, (Self, Self)> {
+        let (input, a) = Self::parse(input)?;
+        let (input, _) = context("expected ' '", tag(" "))(input)?;
+        let (input, outcome) = GameOutcome::parse(input)?;
+        let b = a.other_to_outcome(outcome);
+
+        Ok((input, (a, b)))
+    }
+
+    pub fn score(&self) -> i32 {
+        *self as i32
+    }
+
+    /// The choice's place in [`Ruleset::standard`].
+    pub fn shape(&self) -> Shape {
+        Shape(*self as usize - 1)
+    }
+
+    /// The choice at `shape` in [`Ruleset::standard`]; `None` for shapes only larger rulesets
+    /// have.
+    pub fn from_shape(shape: Shape) -> Option<Self> {
+        match shape.0 {
+            0 => Some(PlayChoice::Rock),
+            1 => Some(PlayChoice::Paper),
+            2 => Some(PlayChoice::Scissors),
+            _ => None,
+        }
+    }
+
+    pub fn game(&self, other: &Self) -> GameOutcome {
+        Ruleset::standard().game(self.shape(), other.shape())
+    }
+
+    pub fn other_to_outcome(&self, outcome: GameOutcome) -> Self {
+        let shape = Ruleset::standard().other_to_outcome(self.shape(), outcome);
+        Self::from_shape(shape).expect("the standard ruleset has three shapes")
+    }
+
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        let (rest, c) = anychar(input)?;
+        match c {
+            'A' => Ok((rest, PlayChoice::Rock)),
+            'B' => Ok((rest, PlayChoice::Paper)),
+            'C' => Ok((rest, PlayChoice::Scissors)),
+            _ => Err(expected(input, "expected 'A', 'B' or 'C'")),
+        }
+    }
+
+    pub fn parse_second(input: &str) -> ParseResult<'_, Self> {
+        let (rest, c) = anychar(input)?;
+        match c {
+            'X' => Ok((rest, PlayChoice::Rock)),
+            'Y' => Ok((rest, PlayChoice::Paper)),
+            'Z' => Ok((rest, PlayChoice::Scissors)),
+            _ => Err(expected(input, "expected 'X', 'Y' or 'Z'")),
+        }
+    }
+}
+
+// implement test
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_game() {
+        assert_eq!(PlayChoice::Rock.game(&PlayChoice::Paper), GameOutcome::Lose);
+        assert_eq!(PlayChoice::Rock.game(&PlayChoice::Rock), GameOutcome::Draw);
+        assert_eq!(
+            PlayChoice::Rock.game(&PlayChoice::Scissors),
+            GameOutcome::Win
+        );
+        assert_eq!(
+            PlayChoice::Scissors.game(&PlayChoice::Scissors),
+            GameOutcome::Draw
+        );
+        assert_eq!(
+            PlayChoice::Scissors.game(&PlayChoice::Rock),
+            GameOutcome::Lose
+        );
+    }
+
+    #[test]
+    fn test_from_shape() {
+        assert_eq!(PlayChoice::from_shape(Shape(1)), Some(PlayChoice::Paper));
+        assert_eq!(PlayChoice::from_shape(Shape(3)), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = include_str!("./sample.txt");
+        let (res, games) = parse_games(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Paper));
+
+        assert_eq!(total_score(&games), 15);
+    }
+
+    #[test]
+    fn test_parse_v2() {
+        let input = include_str!("./sample.txt");
+        let (res, games) = parse_games_v2(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0], (PlayChoice::Rock, PlayChoice::Rock));
+
+        assert_eq!(total_score(&games), 12);
+    }
+}
@@ -0,0 +1,389 @@
+//! Hand games with any odd number of moves arranged in a cycle, where each move beats the
+//! half of the other moves that come just before it: Rock-Paper-Scissors, or
+//! Rock-Spock-Paper-Lizard-Scissors. A ruleset is read from a JSON rules file:
+//!
+//! ```json
+//! {
+//!   "moves": [
+//!     { "name": "rock", "opponent": "A", "player": "X", "score": 1 },
+//!     { "name": "paper", "opponent": "B", "player": "Y", "score": 2 },
+//!     { "name": "scissors", "opponent": "C", "player": "Z", "score": 3 }
+//!   ],
+//!   "outcomes": {
+//!     "lose": { "symbol": "X", "score": 0 },
+//!     "draw": { "symbol": "Y", "score": 3 },
+//!     "win": { "symbol": "Z", "score": 6 }
+//!   }
+//! }
+//! ```
+//!
+//! `outcomes` may be left out, in which case the puzzle's symbols and scores are used.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use nom::{
+    bytes::complete::tag, character::complete::anychar, character::complete::newline,
+    error::context, multi::separated_list1, sequence::separated_pair,
+};
+use serde::{Deserialize, Serialize};
+
+use super::GameOutcome;
+use crate::diagnostics::{expected, ParseResult};
+use crate::error::AocError;
+
+/// A move, as its position in the ruleset's cycle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Move {
+    pub name: String,
+    /// Symbol for the move in the first column of the strategy guide.
+    pub opponent: char,
+    /// Symbol for the move in the second column, when it is read as a move.
+    pub player: char,
+    /// Points for playing the move, whatever the outcome.
+    pub score: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OutcomeRule {
+    /// Symbol for the outcome in the second column, when it is read as an outcome.
+    pub symbol: char,
+    pub score: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Outcomes {
+    pub lose: OutcomeRule,
+    pub draw: OutcomeRule,
+    pub win: OutcomeRule,
+}
+
+impl Default for Outcomes {
+    fn default() -> Self {
+        let rule = |outcome: GameOutcome, symbol| OutcomeRule {
+            symbol,
+            score: outcome.score(),
+        };
+        Self {
+            lose: rule(GameOutcome::Lose, 'X'),
+            draw: rule(GameOutcome::Draw, 'Y'),
+            win: rule(GameOutcome::Win, 'Z'),
+        }
+    }
+}
+
+impl Outcomes {
+    pub fn get(&self, outcome: GameOutcome) -> &OutcomeRule {
+        match outcome {
+            GameOutcome::Lose => &self.lose,
+            GameOutcome::Draw => &self.draw,
+            GameOutcome::Win => &self.win,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Ruleset {
+    /// In cycle order: each move beats the `(len - 1) / 2` moves before it, wrapping around.
+    pub moves: Vec<Move>,
+    #[serde(default)]
+    pub outcomes: Outcomes,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::standard().clone()
+    }
+}
+
+impl Ruleset {
+    /// The puzzle's Rock-Paper-Scissors.
+    pub fn standard() -> &'static Ruleset {
+        static STANDARD: OnceLock<Ruleset> = OnceLock::new();
+        STANDARD.get_or_init(|| {
+            let moves = [
+                ("rock", 'A', 'X'),
+                ("paper", 'B', 'Y'),
+                ("scissors", 'C', 'Z'),
+            ];
+            Ruleset {
+                moves: (1..)
+                    .zip(moves)
+                    .map(|(score, (name, opponent, player))| Move {
+                        name: name.to_string(),
+                        opponent,
+                        player,
+                        score,
+                    })
+                    .collect(),
+                outcomes: Outcomes::default(),
+            }
+        })
+    }
+
+    pub fn new(moves: Vec<Move>, outcomes: Outcomes) -> Result<Self, AocError> {
+        let ruleset = Self { moves, outcomes };
+        ruleset.validate()?;
+        Ok(ruleset)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, AocError> {
+        let ruleset: Ruleset = serde_json::from_str(json)?;
+        ruleset.validate()?;
+        Ok(ruleset)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, AocError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    fn validate(&self) -> Result<(), AocError> {
+        let n = self.moves.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(AocError::InvalidRules(format!(
+                "a cyclic game needs an odd number of moves, at least 3, but has {}",
+                n
+            )));
+        }
+
+        let unique = |what: &str, symbols: Vec<char>| {
+            let mut seen = BTreeSet::new();
+            match symbols.into_iter().find(|s| !seen.insert(*s)) {
+                Some(s) => Err(AocError::InvalidRules(format!(
+                    "symbol '{}' is used for more than one {}",
+                    s, what
+                ))),
+                None => Ok(()),
+            }
+        };
+        let mut names = BTreeSet::new();
+        if let Some(m) = self.moves.iter().find(|m| !names.insert(&m.name)) {
+            return Err(AocError::InvalidRules(format!(
+                "move '{}' is defined twice",
+                m.name
+            )));
+        }
+        unique(
+            "opponent move",
+            self.moves.iter().map(|m| m.opponent).collect(),
+        )?;
+        unique("player move", self.moves.iter().map(|m| m.player).collect())?;
+        let o = &self.outcomes;
+        unique("outcome", vec![o.lose.symbol, o.draw.symbol, o.win.symbol])
+    }
+
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn get(&self, shape: Shape) -> &Move {
+        &self.moves[shape.0]
+    }
+
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.moves.iter().position(|m| m.name == name).map(Shape)
+    }
+
+    /// How a game ends for the player of `player`.
+    pub fn game(&self, player: Shape, opponent: Shape) -> GameOutcome {
+        let n = self.len();
+        match (player.0 + n - opponent.0) % n {
+            0 => GameOutcome::Draw,
+            steps if steps <= n / 2 => GameOutcome::Win,
+            _ => GameOutcome::Lose,
+        }
+    }
+
+    /// The move to play against `opponent` to get `outcome`. With more than three moves
+    /// several moves win (or lose); the one next to `opponent` in the cycle is chosen.
+    pub fn other_to_outcome(&self, opponent: Shape, outcome: GameOutcome) -> Shape {
+        let n = self.len();
+        match outcome {
+            GameOutcome::Draw => opponent,
+            GameOutcome::Win => Shape((opponent.0 + 1) % n),
+            GameOutcome::Lose => Shape((opponent.0 + n - 1) % n),
+        }
+    }
+
+    pub fn game_score(&self, player: Shape, opponent: Shape) -> i32 {
+        self.get(player).score + self.outcomes.get(self.game(player, opponent)).score
+    }
+
+    /// Total score of `(opponent, player)` games.
+    pub fn total_score(&self, games: &[(Shape, Shape)]) -> i32 {
+        games.iter().map(|(a, b)| self.game_score(*b, *a)).sum()
+    }
+
+    pub fn parse_opponent<'a>(&self, input: &'a str) -> ParseResult<'a, Shape> {
+        let (rest, c) = anychar(input)?;
+        match self.moves.iter().position(|m| m.opponent == c) {
+            Some(i) => Ok((rest, Shape(i))),
+            None => Err(expected(input, "expected an opponent move")),
+        }
+    }
+
+    pub fn parse_player<'a>(&self, input: &'a str) -> ParseResult<'a, Shape> {
+        let (rest, c) = anychar(input)?;
+        match self.moves.iter().position(|m| m.player == c) {
+            Some(i) => Ok((rest, Shape(i))),
+            None => Err(expected(input, "expected a player move")),
+        }
+    }
+
+    pub fn parse_outcome<'a>(&self, input: &'a str) -> ParseResult<'a, GameOutcome> {
+        let (rest, c) = anychar(input)?;
+        [GameOutcome::Lose, GameOutcome::Draw, GameOutcome::Win]
+            .into_iter()
+            .find(|o| self.outcomes.get(*o).symbol == c)
+            .map(|o| (rest, o))
+            .ok_or_else(|| expected(input, "expected an outcome"))
+    }
+
+    /// The strategy guide with the second column read as the player's move.
+    pub fn parse_games<'a>(&self, input: &'a str) -> ParseResult<'a, Vec<(Shape, Shape)>> {
+        let pair = separated_pair(
+            |i| self.parse_opponent(i),
+            context("expected ' '", tag(" ")),
+            |i| self.parse_player(i),
+        );
+        separated_list1(newline, pair)(input)
+    }
+
+    /// The strategy guide with the second column read as the outcome to play for.
+    pub fn parse_games_v2<'a>(&self, input: &'a str) -> ParseResult<'a, Vec<(Shape, Shape)>> {
+        let pair = |input: &'a str| {
+            let (input, (a, outcome)) = separated_pair(
+                |i| self.parse_opponent(i),
+                context("expected ' '", tag(" ")),
+                |i| self.parse_outcome(i),
+            )(input)?;
+            Ok((input, (a, self.other_to_outcome(a, outcome))))
+        };
+        separated_list1(newline, pair)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpsls() -> Ruleset {
+        Ruleset::from_json(include_str!("./rpsls.json")).unwrap()
+    }
+
+    /// A ruleset of `n` moves named `m0`, `m1`, ...
+    fn cycle(n: usize) -> Ruleset {
+        let moves = (0..n)
+            .map(|i| Move {
+                name: format!("m{}", i),
+                opponent: (b'A' + i as u8) as char,
+                player: (b'a' + i as u8) as char,
+                score: i as i32 + 1,
+            })
+            .collect();
+        Ruleset::new(moves, Outcomes::default()).unwrap()
+    }
+
+    #[test]
+    fn test_standard_solves_the_puzzle() {
+        let input = include_str!("./sample.txt");
+        let rules = Ruleset::standard();
+        let (_, games) = rules.parse_games(input).unwrap();
+        assert_eq!(rules.total_score(&games), 15);
+        let (_, games) = rules.parse_games_v2(input).unwrap();
+        assert_eq!(rules.total_score(&games), 12);
+    }
+
+    #[test]
+    fn test_rpsls_beats() {
+        let rules = rpsls();
+        let beats = [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ];
+        for (winner, loser) in beats {
+            let (w, l) = (rules.shape(winner).unwrap(), rules.shape(loser).unwrap());
+            assert_eq!(
+                rules.game(w, l),
+                GameOutcome::Win,
+                "{} vs {}",
+                winner,
+                loser
+            );
+            assert_eq!(
+                rules.game(l, w),
+                GameOutcome::Lose,
+                "{} vs {}",
+                loser,
+                winner
+            );
+        }
+    }
+
+    #[test]
+    fn test_rpsls_guide() {
+        let rules = rpsls();
+        let (rest, games) = rules.parse_games("A V\nB Z\nE X").unwrap();
+        assert_eq!(rest, "");
+        // Rock draws with rock, scissors lose to Spock and paper loses to scissors.
+        assert_eq!(rules.total_score(&games), (1 + 3) + 3 + 2);
+
+        let (_, games) = rules.parse_games_v2("D Z").unwrap();
+        assert_eq!(rules.get(games[0].1).name, "scissors");
+    }
+
+    #[test]
+    fn test_other_to_outcome_for_any_ruleset() {
+        for rules in [Ruleset::standard().clone(), rpsls(), cycle(7), cycle(9)] {
+            for opponent in (0..rules.len()).map(Shape) {
+                for outcome in [GameOutcome::Lose, GameOutcome::Draw, GameOutcome::Win] {
+                    let player = rules.other_to_outcome(opponent, outcome);
+                    assert_eq!(rules.game(player, opponent), outcome);
+                }
+                let wins = (0..rules.len())
+                    .filter(|p| rules.game(Shape(*p), opponent) == GameOutcome::Win)
+                    .count();
+                assert_eq!(wins, rules.len() / 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let mut moves = Ruleset::standard().moves.clone();
+        moves.pop();
+        assert!(matches!(
+            Ruleset::new(moves, Outcomes::default()),
+            Err(AocError::InvalidRules(_))
+        ));
+
+        let mut moves = Ruleset::standard().moves.clone();
+        moves[2].player = 'X';
+        assert!(Ruleset::new(moves, Outcomes::default()).is_err());
+
+        let mut moves = Ruleset::standard().moves.clone();
+        moves[2].name = "rock".to_string();
+        assert!(Ruleset::new(moves, Outcomes::default()).is_err());
+
+        assert!(matches!(
+            Ruleset::from_json("{\"moves\": 3}"),
+            Err(AocError::Json(_))
+        ));
+    }
+}
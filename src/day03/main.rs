@@ -1,120 +1,24 @@
-use nom::character::complete::{alphanumeric1, newline};
-use nom::multi::separated_list1;
-use nom::sequence::tuple;
-use nom::IResult;
-
-fn main() {
-    let input = include_str!("./data.txt");
-    let (_, score) = compute_score(input).unwrap();
-    println!("score {}", score);
-
-    let (_, badges_scores) = compute_3_elves_badge_scores(input).unwrap();
-    println!("badges score {}", badges_scores);
-}
-
-fn compute_score(input: &str) -> IResult<&str, u32> {
-    let (res, scores) = separated_list1(newline, parse_line_score)(input)?;
-    let scores = scores.iter().sum();
-    Ok((res, scores))
-}
-
-fn compute_3_elves_badge_scores(input: &str) -> IResult<&str, u32> {
-    let (res, scores) = separated_list1(newline, parse_3_elf_badge)(input)?;
-    let scores = scores.iter().sum();
-    Ok((res, scores))
-}
-
-fn parse_line(input: &str) -> IResult<&str, (&str, &str)> {
-    let (input, line) = alphanumeric1(input)?;
-    let len = line.len() / 2;
-    let left = &line[0..len];
-    let right = &line[len..];
-    Ok((input, (left, right)))
-}
-
-fn parse_3_lines(input: &str) -> IResult<&str, (&str, &str, &str)> {
-    let (input, (elf1, _)) = tuple((alphanumeric1, newline))(input)?;
-    let (input, (elf2, _)) = tuple((alphanumeric1, newline))(input)?;
-    let (input, elf3) = alphanumeric1(input)?;
-    Ok((input, (elf1, elf2, elf3)))
-}
-
-fn parse_3_elf_badge(input: &str) -> IResult<&str, u32> {
-    let (input, (elf1, elf2, elf3)) = parse_3_lines(input)?;
-    for c in elf1.chars() {
-        if elf2.contains(c) && elf3.contains(c) {
-            return Ok((input, char_to_u32(&c)));
-        }
-    }
-    Ok((input, 0))
-}
-
-fn parse_line_score(input: &str) -> IResult<&str, u32> {
-    let (input, (l, r)) = parse_line(input)?;
-    for c in l.chars() {
-        if r.contains(c) {
-            return Ok((input, char_to_u32(&c)));
+use std::process::ExitCode;
+
+use advent_of_code_rust::day03::Day03;
+use advent_of_code_rust::error::AocError;
+use advent_of_code_rust::input;
+use advent_of_code_rust::solution::{parse_all, Solution};
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
         }
     }
-    Ok((input, 0))
 }
 
-fn char_to_u32(c: &char) -> u32 {
-    if *c >= 'A' && *c <= 'Z' {
-        return *c as u32 - 'A' as u32 + 27;
-    }
-    *c as u32 - 'a' as u32 + 1
-}
-
-// test
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_compute_score() {
-        let input = include_str!("./sample.txt");
-        let (_, score) = compute_score(input).unwrap();
-        assert_eq!(score, 157);
-    }
-
-    #[test]
-    fn test_compute_3_elves_bedge_score() {
-        let input = include_str!("./sample.txt");
-        let (_, score) = compute_3_elves_badge_scores(input).unwrap();
-        assert_eq!(score, 70);
-    }
-
-    #[test]
-    fn test_parse_line() {
-        let input = "abcdef";
-        let (res, (left, right)) = parse_line(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(left, "abc");
-        assert_eq!(right, "def");
-    }
-
-    #[test]
-    fn test_parse_duplicated_item() {
-        let input = "abcaef";
-        let (res, value) = parse_line_score(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(value, 1);
-    }
-
-    #[test]
-    fn test_parse_duplicated_item_z() {
-        let input = "zbcazf";
-        let (res, value) = parse_line_score(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(value, 26);
-    }
-
-    #[test]
-    fn test_parse_duplicated_item_a_uppercase() {
-        let input = "AbcazA";
-        let (res, value) = parse_line_score(input).unwrap();
-        assert_eq!(res, "");
-        assert_eq!(value, 27);
-    }
+fn run() -> Result<(), AocError> {
+    let input = input::load(3, std::env::args().nth(1).as_deref())?;
+    let rucksacks = parse_all::<Day03>(&input)?;
+    println!("score {}", Day03::part1(&rucksacks)?);
+    println!("badges score {}", Day03::part2(&rucksacks)?);
+    Ok(())
 }
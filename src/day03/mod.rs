@@ -0,0 +1,177 @@
+use nom::bytes::complete::take_while1;
+use nom::character::complete::newline;
+use nom::error::context;
+use nom::multi::separated_list1;
+use nom::sequence::tuple;
+
+use aoc_macros::aoc;
+
+use crate::diagnostics::ParseResult;
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day03;
+
+#[aoc(day = 3)]
+impl Solution for Day03 {
+    type Input = Vec<String>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        parse_rucksacks(input)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Result<u32, AocError> {
+        compute_score(rucksacks)
+            .ok_or_else(|| AocError::NoAnswer("a rucksack could not be split".to_string()))
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Result<u32, AocError> {
+        compute_3_elves_badge_scores(rucksacks).ok_or_else(|| {
+            AocError::NoAnswer("rucksacks do not form groups of 3 elves".to_string())
+        })
+    }
+}
+
+pub fn parse_rucksacks(input: &str) -> ParseResult<'_, Vec<String>> {
+    let (input, lines) = separated_list1(newline, context("expected a rucksack", items))(input)?;
+    Ok((input, lines.into_iter().map(String::from).collect()))
+}
+
+pub fn compute_score(rucksacks: &[String]) -> Option<u32> {
+    let mut score = 0;
+    for rucksack in rucksacks {
+        let (_, s) = parse_line_score(rucksack).ok()?;
+        score += s;
+    }
+    Some(score)
+}
+
+pub fn compute_3_elves_badge_scores(rucksacks: &[String]) -> Option<u32> {
+    let mut score = 0;
+    for group in rucksacks.chunks(3) {
+        let (_, s) = parse_3_elf_badge(&group.join("\n")).ok()?;
+        score += s;
+    }
+    Some(score)
+}
+
+/// The items of one rucksack: item types are the letters `a-z` and `A-Z`.
+pub fn items(input: &str) -> ParseResult<'_, &str> {
+    context(
+        "expected items 'a'-'z' or 'A'-'Z'",
+        take_while1(|c: char| c.is_ascii_alphabetic()),
+    )(input)
+}
+
+pub fn parse_line(input: &str) -> ParseResult<'_, (&str, &str)> {
+    let (input, line) = items(input)?;
+    let len = line.len() / 2;
+    let left = &line[0..len];
+    let right = &line[len..];
+    Ok((input, (left, right)))
+}
+
+pub fn parse_3_lines(input: &str) -> ParseResult<'_, (&str, &str, &str)> {
+    let (input, (elf1, _)) = tuple((items, newline))(input)?;
+    let (input, (elf2, _)) = tuple((items, newline))(input)?;
+    let (input, elf3) = items(input)?;
+    Ok((input, (elf1, elf2, elf3)))
+}
+
+pub fn parse_3_elf_badge(input: &str) -> ParseResult<'_, u32> {
+    let (input, (elf1, elf2, elf3)) = parse_3_lines(input)?;
+    for c in elf1.chars() {
+        if elf2.contains(c) && elf3.contains(c) {
+            return Ok((input, char_to_u32(&c)));
+        }
+    }
+    Ok((input, 0))
+}
+
+pub fn parse_line_score(input: &str) -> ParseResult<'_, u32> {
+    let (input, (l, r)) = parse_line(input)?;
+    for c in l.chars() {
+        if r.contains(c) {
+            return Ok((input, char_to_u32(&c)));
+        }
+    }
+    Ok((input, 0))
+}
+
+/// An item's priority; anything that is not an item type has none.
+pub fn char_to_u32(c: &char) -> u32 {
+    match c {
+        'a'..='z' => *c as u32 - 'a' as u32 + 1,
+        'A'..='Z' => *c as u32 - 'A' as u32 + 27,
+        _ => 0,
+    }
+}
+
+// test
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_score() {
+        let input = include_str!("./sample.txt");
+        let (_, rucksacks) = parse_rucksacks(input).unwrap();
+        assert_eq!(compute_score(&rucksacks), Some(157));
+    }
+
+    #[test]
+    fn test_compute_3_elves_bedge_score() {
+        let input = include_str!("./sample.txt");
+        let (_, rucksacks) = parse_rucksacks(input).unwrap();
+        assert_eq!(compute_3_elves_badge_scores(&rucksacks), Some(70));
+    }
+
+    #[test]
+    fn test_only_letters_are_items() {
+        let err = crate::solution::parse_all::<Day03>("abab\na1b1").unwrap_err();
+        assert!(matches!(
+            err,
+            AocError::TrailingInput(crate::diagnostics::Diagnostic {
+                line: 2,
+                column: 2,
+                ..
+            })
+        ));
+        assert_eq!(char_to_u32(&'1'), 0);
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let input = "abcdef";
+        let (res, (left, right)) = parse_line(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(left, "abc");
+        assert_eq!(right, "def");
+    }
+
+    #[test]
+    fn test_parse_duplicated_item() {
+        let input = "abcaef";
+        let (res, value) = parse_line_score(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(value, 1);
+    }
+
+    #[test]
+    fn test_parse_duplicated_item_z() {
+        let input = "zbcazf";
+        let (res, value) = parse_line_score(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(value, 26);
+    }
+
+    #[test]
+    fn test_parse_duplicated_item_a_uppercase() {
+        let input = "AbcazA";
+        let (res, value) = parse_line_score(input).unwrap();
+        assert_eq!(res, "");
+        assert_eq!(value, 27);
+    }
+}
@@ -0,0 +1,127 @@
+use nom::bytes::complete::tag;
+use nom::character::complete::{self, newline};
+use nom::error::context;
+use nom::multi::separated_list1;
+use nom::sequence::separated_pair;
+
+use aoc_macros::aoc;
+
+use crate::diagnostics::ParseResult;
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day04;
+
+#[aoc(day = 4)]
+impl Solution for Day04 {
+    type Input = Vec<(Range, Range)>;
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        parse_ranges(input)
+    }
+
+    fn part1(ranges: &Self::Input) -> Result<u32, AocError> {
+        Ok(get_fully_overlapped(ranges))
+    }
+
+    fn part2(ranges: &Self::Input) -> Result<u32, AocError> {
+        Ok(get_partial_overlapped(ranges))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Range {
+    pub start: u32,
+    pub end: u32,
+}
+
+pub fn parse_ranges(input: &str) -> ParseResult<'_, Vec<(Range, Range)>> {
+    separated_list1(newline, Range::parse_pair)(input)
+}
+
+pub fn get_fully_overlapped(ranges: &[(Range, Range)]) -> u32 {
+    ranges
+        .iter()
+        .filter(|(first, second)| first.fully_overlaps(second))
+        .count() as u32
+}
+
+pub fn get_partial_overlapped(ranges: &[(Range, Range)]) -> u32 {
+    ranges
+        .iter()
+        .filter(|(first, second)| first.partial_overlaps(second))
+        .count() as u32
+}
+
+impl Range {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        let section = |input| context("expected a section number", complete::u32)(input);
+        let (input, (start, end)) =
+            separated_pair(section, context("expected '-'", tag("-")), section)(input)?;
+        Ok((input, Self { start, end }))
+    }
+
+    pub fn parse_pair(input: &str) -> ParseResult<'_, (Self, Self)> {
+        separated_pair(Self::parse, context("expected ','", tag(",")), Self::parse)(input)
+    }
+
+    pub fn fully_contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    pub fn fully_overlaps(&self, other: &Self) -> bool {
+        self.fully_contains(other) || other.fully_contains(self)
+    }
+
+    pub fn partial_overlaps(&self, other: &Self) -> bool {
+        !(self.end < other.start || other.end < self.start)
+    }
+}
+
+// test
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_parse() {
+        let input = "1-6";
+        let (input, range) = Range::parse(input).unwrap();
+        assert_eq!(input, "");
+        assert_eq!(range, Range { start: 1, end: 6 });
+    }
+
+    #[test]
+    fn test_couple_parse() {
+        let input = "1-6,4-7";
+        let (input, (first, second)) = Range::parse_pair(input).unwrap();
+        assert_eq!(input, "");
+        assert_eq!(first, Range { start: 1, end: 6 });
+        assert_eq!(second, Range { start: 4, end: 7 });
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(!Range { start: 1, end: 6 }.fully_overlaps(&Range { start: 4, end: 7 }));
+        assert!(Range { start: 1, end: 6 }.fully_overlaps(&Range { start: 2, end: 3 }));
+        assert!(Range { start: 1, end: 6 }.fully_overlaps(&Range { start: 1, end: 9 }));
+    }
+
+    #[test]
+    fn test_fully_overlapped() {
+        let input = include_str!("./sample.txt");
+        let (_, ranges) = parse_ranges(input).unwrap();
+        let res = get_fully_overlapped(&ranges);
+        assert_eq!(res, 2);
+    }
+
+    #[test]
+    fn test_partially_overlapped() {
+        let input = include_str!("./sample.txt");
+        let (_, ranges) = parse_ranges(input).unwrap();
+        let res = get_partial_overlapped(&ranges);
+        assert_eq!(res, 4);
+    }
+}
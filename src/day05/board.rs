@@ -1,18 +1,21 @@
 use nom::{
     bytes::complete::tag,
     character::complete::{anychar, newline, u16 as parse_u16},
+    error::context,
     multi::separated_list1,
     sequence::tuple,
-    IResult,
 };
 use std::fmt;
 
-#[derive(Debug, PartialEq)]
+use crate::diagnostics::{expected, ParseResult};
+use crate::error::AocError;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Board {
     pub tiles: Vec<Vec<TileElement>>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TileElement {
     pub val: char,
 }
@@ -41,18 +44,28 @@ impl Board {
         res
     }
 
-    pub fn parse(input: &str) -> IResult<&str, Self> {
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
         let mut parse_rows = separated_list1(newline, parse_row);
         let (input, mut rows) = parse_rows(input)?;
         let (input, _) = newline(input)?;
-        let (input, cnt) = parse_index(input)?;
+        let numbers = input;
+        let (input, cnt) = context("expected the stack numbers", parse_index)(input)?;
         rows.reverse();
 
+        // Editors often strip the trailing blanks of a row, so a short row just has empty
+        // slots on the right; a crate beyond the last stack is an error.
+        if rows
+            .iter()
+            .any(|row| row.iter().skip(cnt).any(Option::is_some))
+        {
+            return Err(expected(numbers, "expected a number for every stack"));
+        }
+
         let mut tiles: Vec<Vec<TileElement>> = vec![];
         for i in 0..cnt {
             let mut tile: Vec<TileElement> = vec![];
             for row in rows.iter() {
-                if let Some(el) = &row[i] {
+                if let Some(el) = row.get(i).and_then(Option::as_ref) {
                     tile.push(TileElement::new(el.val));
                 }
             }
@@ -79,16 +92,52 @@ impl Board {
         None
     }
 
-    pub fn move_el(&mut self, from: usize, to: usize) {
-        let tile = self.tiles[from].pop().unwrap();
+    pub fn move_el(&mut self, from: usize, to: usize) -> Result<(), AocError> {
+        self.check_stack(to)?;
+        let tile = self
+            .stack_mut(from)?
+            .pop()
+            .ok_or(AocError::EmptyStack { stack: from })?;
         self.tiles[to].push(tile);
+        Ok(())
     }
 
-    pub fn move_many_el(&mut self, count: usize, from: usize, to: usize) {
-        let len = self.tiles[from].len();
-        let from_range = len - count;
-        let moved: Vec<_> = self.tiles[from].splice(from_range..len, vec![]).collect();
+    pub fn move_many_el(&mut self, count: usize, from: usize, to: usize) -> Result<(), AocError> {
+        self.check_stack(to)?;
+        let stack = self.stack_mut(from)?;
+        let len = stack.len();
+        let from_range = len
+            .checked_sub(count)
+            .ok_or(AocError::EmptyStack { stack: from })?;
+        let moved: Vec<_> = stack.splice(from_range..len, vec![]).collect();
         self.tiles[to].extend(moved);
+        Ok(())
+    }
+
+    /// Reverses the order of the top `count` crates of `stack`.
+    pub fn reverse_top(&mut self, count: usize, stack: usize) -> Result<(), AocError> {
+        let tiles = self.stack_mut(stack)?;
+        let len = tiles.len();
+        let start = len
+            .checked_sub(count)
+            .ok_or(AocError::EmptyStack { stack })?;
+        tiles[start..].reverse();
+        Ok(())
+    }
+
+    fn check_stack(&self, stack: usize) -> Result<(), AocError> {
+        if stack >= self.tiles.len() {
+            return Err(AocError::InvalidStack {
+                stack,
+                stacks: self.tiles.len(),
+            });
+        }
+        Ok(())
+    }
+
+    fn stack_mut(&mut self, stack: usize) -> Result<&mut Vec<TileElement>, AocError> {
+        self.check_stack(stack)?;
+        Ok(&mut self.tiles[stack])
     }
 }
 
@@ -112,33 +161,33 @@ impl fmt::Display for Board {
     }
 }
 
-fn parse_row(input: &str) -> IResult<&str, Vec<Option<TileElement>>> {
+fn parse_row(input: &str) -> ParseResult<'_, Vec<Option<TileElement>>> {
     separated_list1(tag(" "), parse_element)(input)
 }
 
-fn parse_index(input: &str) -> IResult<&str, usize> {
+fn parse_index(input: &str) -> ParseResult<'_, usize> {
     let (input, list) = separated_list1(tag(" "), tuple((tag(" "), parse_u16, tag(" "))))(input)?;
     Ok((input, list.len()))
 }
 
-fn parse_element(input: &str) -> IResult<&str, Option<TileElement>> {
+fn parse_element(input: &str) -> ParseResult<'_, Option<TileElement>> {
     if let Ok((input, el)) = _parse_existing(input) {
         return Ok((input, Some(el)));
     }
 
-    match _parse_empty(input) {
+    match context("expected a crate like '[A]' or an empty slot", _parse_empty)(input) {
         Ok((input, _)) => Ok((input, None)),
         Err(e) => Err(e),
     }
 }
 
-fn _parse_existing(input: &str) -> IResult<&str, TileElement> {
+fn _parse_existing(input: &str) -> ParseResult<'_, TileElement> {
     let (input, (_, val, _)) = tuple((tag("["), anychar, tag("]")))(input)?;
     let el = TileElement::new(val);
     Ok((input, el))
 }
 
-fn _parse_empty(input: &str) -> IResult<&str, ()> {
+fn _parse_empty(input: &str) -> ParseResult<'_, ()> {
     let (input, _) = tag("   ")(input)?;
     Ok((input, ()))
 }
@@ -172,6 +221,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_ragged_rows() {
+        let input = include_str!("./sample.txt").replace("    [D]    ", "    [D]");
+        let (_, board) = Board::parse(&input).unwrap();
+        assert_eq!(board, Board::parse(include_str!("./sample.txt")).unwrap().1);
+
+        let (_, board) = Board::parse("[A]\n 1   2 ").unwrap();
+        assert_eq!(board.tiles, vec![vec![TileElement::new('A')], vec![]]);
+
+        assert!(Board::parse("[A]     [C]\n 1   2 ").is_err());
+    }
+
     #[test]
     fn test_parse_board() {
         let input = include_str!("./sample.txt");
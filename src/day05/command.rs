@@ -1,54 +1,69 @@
 use nom::{
     bytes::complete::tag,
     character::complete::{newline, u16 as parse_u16},
+    combinator::verify,
+    error::context,
     multi::separated_list1,
-    IResult,
 };
 
 use super::board::Board;
+use crate::diagnostics::ParseResult;
+use crate::error::AocError;
 
 #[derive(Debug, PartialEq)]
 pub struct Command {
-    size: usize,
-    from: usize,
-    to: usize,
+    pub size: usize,
+    pub from: usize,
+    pub to: usize,
 }
 
 impl Command {
-    pub fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, _) = tag("move ")(input)?;
-        let (input, size) = parse_u16(input)?;
-        let (input, _) = tag(" from ")(input)?;
-        let (input, from) = parse_u16(input)?;
-        let (input, _) = tag(" to ")(input)?;
-        let (input, to) = parse_u16(input)?;
+    pub fn parse(input: &str) -> ParseResult<'_, Self> {
+        let (input, _) = context("expected 'move '", tag("move "))(input)?;
+        let (input, size) = context("expected a crate count", parse_u16)(input)?;
+        let (input, _) = context("expected ' from '", tag(" from "))(input)?;
+        let (input, from) = parse_stack(input)?;
+        let (input, _) = context("expected ' to '", tag(" to "))(input)?;
+        let (input, to) = parse_stack(input)?;
         Ok((
             input,
             Self {
                 size: size as usize,
-                from: from as usize - 1,
-                to: to as usize - 1,
+                from,
+                to,
             },
         ))
     }
 
-    pub fn execute(&self, board: &mut Board) {
+    pub fn execute(&self, board: &mut Board) -> Result<(), AocError> {
         for _ in 0..self.size {
-            board.move_el(self.from, self.to);
+            board.move_el(self.from, self.to)?;
         }
+        Ok(())
     }
 
-    pub fn execute_v2(&self, board: &mut Board) {
+    pub fn execute_v2(&self, board: &mut Board) -> Result<(), AocError> {
         board.move_many_el(self.size, self.from, self.to)
     }
 
-    pub fn parse_many(input: &str) -> IResult<&str, Vec<Self>> {
+    pub fn parse_many(input: &str) -> ParseResult<'_, Vec<Self>> {
         separated_list1(newline, Self::parse)(input)
     }
 }
 
+/// Stacks are numbered from 1 in the input but indexed from 0 on the board.
+fn parse_stack(input: &str) -> ParseResult<'_, usize> {
+    let (input, stack) = context(
+        "expected a stack number starting from 1",
+        verify(parse_u16, |n: &u16| *n > 0),
+    )(input)?;
+    Ok((input, stack as usize - 1))
+}
+
+#[cfg(test)]
 mod tests {
-    use crate::board::TileElement;
+    use crate::day05::board::TileElement;
+    use crate::diagnostics::Diagnostic;
 
     use super::*;
 
@@ -79,7 +94,7 @@ mod tests {
             to: 1,
         };
 
-        command.execute(&mut board);
+        command.execute(&mut board).unwrap();
 
         let expected = Board {
             tiles: vec![vec![TileElement::new('Z')], vec![TileElement::new('N')]],
@@ -87,4 +102,61 @@ mod tests {
 
         assert_eq!(board, expected)
     }
+
+    #[test]
+    fn test_parse_command_diagnostic() {
+        let input = "move 5 frm 1 to 3";
+        let err = Command::parse(input).unwrap_err();
+        let diagnostic = Diagnostic::from_nom(input, &err);
+        assert_eq!(diagnostic.column, 7);
+        assert_eq!(diagnostic.expected.as_deref(), Some("expected ' from '"));
+    }
+
+    #[test]
+    fn test_parse_stack_zero() {
+        assert!(Command::parse("move 1 from 0 to 3").is_err());
+    }
+
+    #[test]
+    fn test_command_empty_stack() {
+        let mut board = Board {
+            tiles: vec![vec![TileElement::new('Z')], vec![]],
+        };
+
+        let command = Command {
+            size: 2,
+            from: 0,
+            to: 1,
+        };
+
+        assert!(matches!(
+            command.execute(&mut board),
+            Err(AocError::EmptyStack { stack: 0 })
+        ));
+        assert!(matches!(
+            command.execute_v2(&mut board),
+            Err(AocError::EmptyStack { stack: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_command_invalid_stack() {
+        let mut board = Board {
+            tiles: vec![vec![TileElement::new('Z')]],
+        };
+
+        let command = Command {
+            size: 1,
+            from: 0,
+            to: 4,
+        };
+
+        assert!(matches!(
+            command.execute(&mut board),
+            Err(AocError::InvalidStack {
+                stack: 4,
+                stacks: 1
+            })
+        ));
+    }
 }
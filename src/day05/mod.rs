@@ -0,0 +1,95 @@
+use nom::bytes::complete::tag;
+use nom::error::context;
+use nom::sequence::separated_pair;
+
+pub mod board;
+pub mod command;
+
+pub use board::{Board, TileElement};
+pub use command::Command;
+
+use aoc_macros::aoc;
+
+use crate::diagnostics::ParseResult;
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day05;
+
+#[aoc(day = 5)]
+impl Solution for Day05 {
+    type Input = (Board, Vec<Command>);
+    type Output1 = String;
+    type Output2 = String;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        parse_board_and_commands(input)
+    }
+
+    fn part1((board, commands): &Self::Input) -> Result<String, AocError> {
+        let mut board = board.clone();
+        for c in commands {
+            c.execute(&mut board)?;
+        }
+
+        Ok(board.first_row())
+    }
+
+    fn part2((board, commands): &Self::Input) -> Result<String, AocError> {
+        let mut board = board.clone();
+        for c in commands {
+            c.execute_v2(&mut board)?;
+        }
+
+        Ok(board.first_row())
+    }
+
+    /// Only the commands can be left over: the board must be followed by a blank line.
+    fn parse_leftover(input: &str) -> ParseResult<'_, ()> {
+        Command::parse_many(input).map(|(rest, _)| (rest, ()))
+    }
+}
+
+pub fn parse_board_and_commands(input: &str) -> ParseResult<'_, (Board, Vec<Command>)> {
+    let blank_line = context("expected a blank line after the board", tag("\n\n"));
+    separated_pair(Board::parse, blank_line, Command::parse_many)(input)
+}
+
+/// Part 2 with the part 1 crane: move crates one at a time, then flip the moved ones back.
+#[aoc(day = 5, part = 2, name = "one_at_a_time")]
+pub fn part2_one_at_a_time((board, commands): &(Board, Vec<Command>)) -> Result<String, AocError> {
+    let mut board = board.clone();
+    for c in commands {
+        c.execute(&mut board)?;
+        board.reverse_top(c.size, c.to)?;
+    }
+
+    Ok(board.first_row())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample() {
+        let input = include_str!("./sample.txt");
+        let (_, (mut board, commands)) = parse_board_and_commands(input).unwrap();
+        for c in commands {
+            c.execute(&mut board).unwrap();
+        }
+
+        assert_eq!("CMZ", board.first_row());
+    }
+
+    #[test]
+    fn test_sample_v2() {
+        let input = include_str!("./sample.txt");
+        let (_, (mut board, commands)) = parse_board_and_commands(input).unwrap();
+        for c in commands {
+            c.execute_v2(&mut board).unwrap();
+        }
+
+        assert_eq!("MCD", board.first_row());
+    }
+}
@@ -0,0 +1,139 @@
+use nom::branch::alt;
+use nom::bytes::complete::take_while_m_n;
+use nom::character::complete::{alphanumeric1, anychar};
+use nom::error::context;
+
+use aoc_macros::aoc;
+
+use crate::diagnostics::{expected, ParseResult};
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day06;
+
+#[aoc(day = 6)]
+impl Solution for Day06 {
+    type Input = String;
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input> {
+        let (input, signal) = context("expected a signal", alphanumeric1)(input)?;
+        Ok((input, signal.to_string()))
+    }
+
+    fn part1(signal: &Self::Input) -> Result<usize, AocError> {
+        parse_marker_or_consume_char(signal)
+            .ok_or_else(|| AocError::NoAnswer("no start-of-packet marker".to_string()))
+    }
+
+    fn part2(signal: &Self::Input) -> Result<usize, AocError> {
+        parse_message_marker_or_consume_char(signal)
+            .ok_or_else(|| AocError::NoAnswer("no start-of-message marker".to_string()))
+    }
+}
+
+pub fn parse_marker_or_consume_char(input: &str) -> Option<usize> {
+    parse_n_marker_or_consume_char(input, 4)
+}
+
+pub fn parse_message_marker_or_consume_char(input: &str) -> Option<usize> {
+    parse_n_marker_or_consume_char(input, 14)
+}
+
+pub fn parse_n_marker_or_consume_char(input: &str, len: usize) -> Option<usize> {
+    let mut inp = input;
+    for i in 0..input.len() {
+        let (res, c) = alt((parse_marker(len), consume_char))(inp).ok()?;
+        if c.len() == len {
+            return Some(i + len);
+        }
+        inp = res
+    }
+
+    None
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+pub fn parse_marker(len: usize) -> impl Fn(&str) -> ParseResult<'_, String> {
+    move |input| {
+        let (input, marker) = take_while_m_n(len, len, is_digit)(input)?;
+        if check_if_duplicates(marker) {
+            return Err(expected(input, "expected a marker without duplicates"));
+        }
+        Ok((input, marker.to_string()))
+    }
+}
+
+pub fn check_if_duplicates(input: &str) -> bool {
+    if input.len() <= 1 {
+        return false;
+    }
+
+    let first = input.chars().next().unwrap();
+    let substr = &input[1..];
+    for c in substr.chars() {
+        if c == first {
+            return true;
+        }
+    }
+
+    check_if_duplicates(&input[1..])
+}
+
+pub fn consume_char(input: &str) -> ParseResult<'_, String> {
+    let (input, c) = anychar(input)?;
+    Ok((input, c.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_marker_or_consume_char() {
+        assert_eq!(
+            parse_marker_or_consume_char("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            Some(5)
+        );
+        assert_eq!(
+            parse_marker_or_consume_char("nppdvjthqldpwncqszvftbrmjlhg"),
+            Some(6)
+        );
+        assert_eq!(
+            parse_marker_or_consume_char("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
+            Some(10)
+        );
+        assert_eq!(
+            parse_marker_or_consume_char("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn test_parse_message_marker_or_consume_char() {
+        assert_eq!(
+            parse_message_marker_or_consume_char("mjqjpqmgbljsphdztnvjfqwrcgsmlb"),
+            Some(19)
+        );
+        assert_eq!(
+            parse_message_marker_or_consume_char("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+            Some(23)
+        );
+        assert_eq!(
+            parse_message_marker_or_consume_char("nppdvjthqldpwncqszvftbrmjlhg"),
+            Some(23)
+        );
+        assert_eq!(
+            parse_message_marker_or_consume_char("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"),
+            Some(29)
+        );
+        assert_eq!(
+            parse_message_marker_or_consume_char("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"),
+            Some(26)
+        );
+    }
+}
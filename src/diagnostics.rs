@@ -0,0 +1,118 @@
+use std::fmt;
+
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+use nom::{IResult, Offset};
+
+/// Result type shared by every day's parsers, so failures can be turned into a [`Diagnostic`].
+pub type ParseResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Builds a recoverable parse error at `input` carrying an "expected ..." message.
+pub fn expected<'a>(input: &'a str, message: &'static str) -> nom::Err<VerboseError<&'a str>> {
+    nom::Err::Error(VerboseError {
+        errors: vec![(input, VerboseErrorKind::Context(message))],
+    })
+}
+
+/// A parse failure located in the original input, rendered with the offending line and a caret.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub offset: usize,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub source_line: String,
+    pub expected: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn at(input: &str, offset: usize, expected: Option<String>) -> Self {
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|i| offset + i)
+            .unwrap_or(input.len());
+
+        Self {
+            offset,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line: input[line_start..line_end].to_string(),
+            expected,
+        }
+    }
+
+    /// Locates a nom failure, preferring the innermost `context` message as the expectation.
+    pub fn from_nom(input: &str, err: &nom::Err<VerboseError<&str>>) -> Self {
+        let e = match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => e,
+            nom::Err::Incomplete(_) => {
+                return Self::at(input, input.len(), Some("more input".to_string()))
+            }
+        };
+
+        let located = e
+            .errors
+            .iter()
+            .find(|(_, kind)| matches!(kind, VerboseErrorKind::Context(_)))
+            .or_else(|| e.errors.first());
+
+        match located {
+            Some((rest, kind)) => {
+                let expected = match kind {
+                    VerboseErrorKind::Context(message) => message.to_string(),
+                    VerboseErrorKind::Char(c) => format!("expected '{}'", c),
+                    VerboseErrorKind::Nom(ErrorKind::Eof) => "unexpected end of input".to_string(),
+                    VerboseErrorKind::Nom(kind) => format!("expected {}", kind.description()),
+                };
+                Self::at(input, input.offset(rest), Some(expected))
+            }
+            None => Self::at(input, 0, None),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)?;
+        if let Some(expected) = &self.expected {
+            write!(f, ": {}", expected)?;
+        }
+        writeln!(f)?;
+
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}^", gutter, " ".repeat(self.column - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::bytes::complete::tag;
+    use nom::error::context;
+
+    #[test]
+    fn test_locates_line_and_column() {
+        let input = "move 1 from 2 to 1\nmove 3 frm 1 to 3";
+        let d = Diagnostic::at(input, 26, None);
+        assert_eq!(d.line, 2);
+        assert_eq!(d.column, 8);
+        assert_eq!(d.source_line, "move 3 frm 1 to 3");
+    }
+
+    #[test]
+    fn test_reports_context() {
+        let input = "move 3 frm 1 to 3";
+        let err = context("expected ' from '", tag(" from "))(&input[6..])
+            .map(|(_, o): (&str, &str)| o)
+            .unwrap_err();
+        let d = Diagnostic::from_nom(input, &err);
+        assert_eq!(
+            d.to_string(),
+            "line 1, column 7: expected ' from '\n  |\n1 | move 3 frm 1 to 3\n  |       ^"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+use std::io;
+
+use nom::error::VerboseError;
+
+use crate::diagnostics::Diagnostic;
+
+/// Everything that can go wrong while loading, parsing or solving a puzzle.
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The puzzle site could not be reached or refused the request.
+    Http(String),
+    /// A parser failed; the diagnostic points at where it gave up.
+    Parse(Diagnostic),
+    /// The parser succeeded but left non-whitespace input behind; the diagnostic points at it.
+    TrailingInput(Diagnostic),
+    /// A crate was taken from a stack that had none left.
+    EmptyStack {
+        stack: usize,
+    },
+    /// A command referenced a stack that is not on the board.
+    InvalidStack {
+        stack: usize,
+        stacks: usize,
+    },
+    /// A day 2 rules file does not describe a playable cyclic game.
+    InvalidRules(String),
+    /// The input was valid but did not contain an answer.
+    NoAnswer(String),
+}
+
+impl AocError {
+    pub fn from_nom(input: &str, err: nom::Err<VerboseError<&str>>) -> Self {
+        AocError::Parse(Diagnostic::from_nom(input, &err))
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "{}", e),
+            AocError::Json(e) => write!(f, "invalid JSON: {}", e),
+            AocError::Http(e) => write!(f, "{}", e),
+            AocError::Parse(diagnostic) => write!(f, "parse error at {}", diagnostic),
+            AocError::TrailingInput(diagnostic) => {
+                write!(f, "unparsed input at {}", diagnostic)
+            }
+            AocError::EmptyStack { stack } => write!(f, "stack {} is empty", stack + 1),
+            AocError::InvalidStack { stack, stacks } => write!(
+                f,
+                "stack {} does not exist, the board has {} stacks",
+                stack + 1,
+                stacks
+            ),
+            AocError::InvalidRules(reason) => write!(f, "invalid rules: {}", reason),
+            AocError::NoAnswer(reason) => write!(f, "no answer: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AocError {
+    fn from(e: serde_json::Error) -> Self {
+        AocError::Json(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_nom_offset() {
+        let input = "move 1 frm 2 to 3";
+        let err = crate::diagnostics::expected(&input[6..], "expected ' from '");
+        assert!(matches!(
+            AocError::from_nom(input, err),
+            AocError::Parse(Diagnostic { offset: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn test_display_is_one_indexed() {
+        assert_eq!(
+            AocError::EmptyStack { stack: 0 }.to_string(),
+            "stack 1 is empty"
+        );
+    }
+}
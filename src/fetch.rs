@@ -0,0 +1,309 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::AocError;
+use crate::input;
+
+pub const YEAR: u16 = 2022;
+
+/// Site the inputs are downloaded from, unless `AOC_BASE_URL` is set.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Session token file in the repository root; it is gitignored.
+pub const SESSION_FILE: &str = ".aoc-session";
+
+/// Minimum time between two requests to the site, shared by every `aoc` invocation.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/ludusrusso/advent-of-code-rust-2022";
+
+/// Looks for the session cookie in `AOC_SESSION`, then `.aoc-session`, then
+/// `~/.config/aoc/session`.
+pub fn session_token() -> Result<String, AocError> {
+    if let Ok(token) = std::env::var("AOC_SESSION") {
+        if !token.trim().is_empty() {
+            return Ok(token.trim().to_string());
+        }
+    }
+
+    let mut candidates = vec![PathBuf::from(SESSION_FILE)];
+    if let Ok(home) = std::env::var("HOME") {
+        candidates.push(Path::new(&home).join(".config/aoc/session"));
+    }
+    for path in candidates {
+        if let Ok(token) = fs::read_to_string(&path) {
+            if !token.trim().is_empty() {
+                return Ok(token.trim().to_string());
+            }
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!(
+            "no session token: set AOC_SESSION or write it to {}",
+            SESSION_FILE
+        ),
+    )
+    .into())
+}
+
+/// Spaces requests out by at least `interval`. The time of the last request is kept in
+/// `stamp`, so the limit holds across separate runs.
+pub struct RateLimiter {
+    pub interval: Duration,
+    pub stamp: PathBuf,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration, stamp: PathBuf) -> Self {
+        Self { interval, stamp }
+    }
+
+    /// Stamp file next to the cached inputs.
+    pub fn in_input_dir() -> Self {
+        let dir = input::default_path(1)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Self::new(MIN_INTERVAL, dir.join(".last-request"))
+    }
+
+    /// Sleeps until the interval since the last request has passed, then records a new one.
+    pub fn wait(&self) -> Result<(), AocError> {
+        if let Some(last) = fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+        {
+            let next = Duration::from_millis(last) + self.interval;
+            if let Some(remaining) = next.checked_sub(now()) {
+                thread::sleep(remaining);
+            }
+        }
+
+        if let Some(dir) = self.stamp.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.stamp, now().as_millis().to_string())?;
+        Ok(())
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Authenticated client for the puzzle site.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    limiter: RateLimiter,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: String, limiter: RateLimiter) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            limiter,
+        }
+    }
+
+    /// Client for the real site (or `AOC_BASE_URL`), using [`session_token`].
+    pub fn from_env() -> Result<Self, AocError> {
+        let base_url = std::env::var("AOC_BASE_URL").unwrap_or_else(|_| BASE_URL.to_string());
+        Ok(Self::new(
+            &base_url,
+            session_token()?,
+            RateLimiter::in_input_dir(),
+        ))
+    }
+
+    fn url(&self, day: u8, path: &str) -> String {
+        format!("{}/{}/day/{}{}", self.base_url, YEAR, day, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    /// Downloads the puzzle input for `day`.
+    pub fn download(&self, day: u8) -> Result<String, AocError> {
+        self.limiter.wait()?;
+        let url = self.url(day, "/input");
+        let response = self.agent.get(&url).header("Cookie", self.cookie()).call();
+        read_body(day, &url, response)
+    }
+
+    /// Posts `answer` for one part of `day` and returns the response page.
+    pub fn post_answer(&self, day: u8, part: u8, answer: &str) -> Result<String, AocError> {
+        self.limiter.wait()?;
+        let url = self.url(day, "/answer");
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)]);
+        read_body(day, &url, response)
+    }
+}
+
+fn read_body(
+    day: u8,
+    url: &str,
+    response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, AocError> {
+    let http_error = |e: ureq::Error| AocError::Http(format!("{}: {}", url, e));
+    let mut response = response.map_err(http_error)?;
+    let status = response.status().as_u16();
+    let body = response.body_mut().read_to_string().map_err(http_error)?;
+
+    match status {
+        200 => Ok(body),
+        404 => Err(AocError::Http(format!(
+            "day {} is not unlocked yet ({})",
+            day, url
+        ))),
+        400 | 500 => Err(AocError::Http(format!(
+            "{} returned {}, is the session token still valid?",
+            url, status
+        ))),
+        _ => Err(AocError::Http(format!(
+            "{} returned {}: {}",
+            url,
+            status,
+            body.lines().next().unwrap_or_default()
+        ))),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    /// The input was already on disk and the site was not contacted.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure `path` holds the input for `day`, downloading it only if the file is
+/// missing or empty.
+pub fn fetch_to(client: &Client, day: u8, path: &Path) -> Result<Fetched, AocError> {
+    if fs::metadata(path).map(|m| m.len() > 0).unwrap_or(false) {
+        return Ok(Fetched::Cached(path.to_path_buf()));
+    }
+
+    let input = client.download(day)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input)?;
+    Ok(Fetched::Downloaded(path.to_path_buf()))
+}
+
+/// Fetches the input for `day` into the default inputs directory.
+pub fn fetch(client: &Client, day: u8) -> Result<Fetched, AocError> {
+    fetch_to(client, day, &input::default_path(day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::MockServer;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn client(url: &str, dir: &Path) -> Client {
+        let limiter = RateLimiter::new(Duration::ZERO, dir.join(".last-request"));
+        Client::new(url, "abc123".to_string(), limiter)
+    }
+
+    #[test]
+    fn test_downloads_once_then_uses_cache() {
+        let dir = temp_dir("cache");
+        let server = MockServer::start(vec![(200, "1000\n2000\n\n3000\n")]);
+        let client = client(&server.url, &dir);
+        let path = dir.join("day01.txt");
+
+        assert_eq!(
+            fetch_to(&client, 1, &path).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(
+            fetch_to(&client, 1, &path).unwrap(),
+            Fetched::Cached(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n\n3000\n");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+        assert_eq!(requests[0].header("user-agent"), Some(USER_AGENT));
+        assert!(requests[0].body.is_empty());
+    }
+
+    #[test]
+    fn test_empty_file_is_not_a_cache_hit() {
+        let dir = temp_dir("empty");
+        let path = dir.join("day06.txt");
+        fs::write(&path, "").unwrap();
+        let server = MockServer::start(vec![(200, "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n")]);
+
+        let fetched = fetch_to(&client(&server.url, &dir), 6, &path).unwrap();
+        assert_eq!(fetched, Fetched::Downloaded(path));
+        assert_eq!(server.requests()[0].path, "/2022/day/6/input");
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let dir = temp_dir("errors");
+        let path = dir.join("day25.txt");
+        let server = MockServer::start(vec![
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (400, ""),
+        ]);
+        let client = client(&server.url, &dir);
+
+        let err = fetch_to(&client, 25, &path).unwrap_err();
+        assert!(err.to_string().contains("not unlocked yet"));
+        assert!(!path.exists());
+
+        let err = fetch_to(&client, 25, &path).unwrap_err();
+        assert!(err.to_string().contains("session token"));
+    }
+
+    #[test]
+    fn test_rate_limiter_waits_between_requests() {
+        let dir = temp_dir("limiter");
+        let limiter = RateLimiter::new(Duration::from_millis(200), dir.join(".last-request"));
+
+        let start = std::time::Instant::now();
+        limiter.wait().unwrap();
+        assert!(start.elapsed() < Duration::from_millis(200));
+        limiter.wait().unwrap();
+        // The stamp is stored in whole milliseconds.
+        assert!(start.elapsed() >= Duration::from_millis(199));
+    }
+}
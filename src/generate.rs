@@ -0,0 +1,293 @@
+//! Seeded generators of valid puzzle inputs of any size, for stress tests and
+//! `aoc bench --synthetic`. The same seed always produces the same input.
+
+/// SplitMix64: tiny, fast and good enough to shuffle puzzle inputs.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`; `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn between(&mut self, lo: usize, hi: usize) -> usize {
+        lo + self.below(hi - lo + 1)
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// An input for `day` of roughly `size` records (elves, lines, moves, characters).
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<String> {
+    let rng = &mut Rng::new(seed);
+    let size = size.max(1);
+    match day {
+        1 => Some(day01(rng, size)),
+        2 => Some(day02(rng, size)),
+        3 => Some(day03(rng, size)),
+        4 => Some(day04(rng, size)),
+        5 => Some(day05(rng, size)),
+        6 => Some(day06(rng, size).signal),
+        _ => None,
+    }
+}
+
+/// `elves` groups of calorie counts separated by blank lines.
+pub fn day01(rng: &mut Rng, elves: usize) -> String {
+    (0..elves)
+        .map(|_| {
+            (0..rng.between(1, 15))
+                .map(|_| rng.between(1000, 60000).to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// `rounds` lines of a strategy guide.
+pub fn day02(rng: &mut Rng, rounds: usize) -> String {
+    (0..rounds)
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Rucksacks in groups of three (`rucksacks` is rounded up). The two compartments of each
+/// rucksack share exactly one item type, and each group shares exactly one badge.
+pub fn day03(rng: &mut Rng, rucksacks: usize) -> String {
+    let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut lines = vec![];
+
+    for _ in 0..rucksacks.div_ceil(3) {
+        let mut items = items.clone();
+        rng.shuffle(&mut items);
+        let (badge, rest) = items.split_first().unwrap();
+
+        // Each elf gets 17 item types no other elf in the group has, so only the badge is
+        // common to all three.
+        for own in rest.chunks(17) {
+            let (shared, own) = own.split_first().unwrap();
+            let (left_only, right_only) = own.split_at(8);
+            let extra = rng.between(0, 6);
+
+            let mut left = vec![*shared, *badge];
+            left.extend((0..extra).map(|_| rng.pick(left_only)));
+            let mut right = vec![*shared];
+            right.extend((0..=extra).map(|_| rng.pick(right_only)));
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            lines.push(left.into_iter().chain(right).collect::<String>());
+        }
+    }
+    lines.join("\n")
+}
+
+/// `pairs` lines of section range pairs.
+pub fn day04(rng: &mut Rng, pairs: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = rng.between(1, 99);
+        format!("{}-{}", start, rng.between(start, 99))
+    };
+    (0..pairs)
+        .map(|_| format!("{},{}", range(rng), range(rng)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A board of up to nine stacks followed by `moves` moves that never take more crates than
+/// a stack holds, whichever crane runs them.
+pub fn day05(rng: &mut Rng, moves: usize) -> String {
+    let crates: Vec<char> = ('A'..='Z').collect();
+    let stacks = rng.between(3, 9);
+    let mut heights: Vec<usize> = (0..stacks).map(|_| rng.between(1, 8)).collect();
+    let board: Vec<Vec<char>> = heights
+        .iter()
+        .map(|h| (0..*h).map(|_| rng.pick(&crates)).collect())
+        .collect();
+
+    let mut lines = vec![];
+    let top = heights.iter().copied().max().unwrap_or(0);
+    for row in (0..top).rev() {
+        let cells: Vec<String> = board
+            .iter()
+            .map(|stack| match stack.get(row) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            })
+            .collect();
+        lines.push(cells.join(" "));
+    }
+    lines.push(
+        (1..=stacks)
+            .map(|n| format!(" {} ", n))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    lines.push(String::new());
+
+    for _ in 0..moves {
+        let non_empty: Vec<usize> = (0..stacks).filter(|s| heights[*s] > 0).collect();
+        let from = rng.pick(&non_empty);
+        let to = (from + rng.between(1, stacks - 1)) % stacks;
+        let size = rng.between(1, heights[from].min(5));
+        heights[from] -= size;
+        heights[to] += size;
+        lines.push(format!("move {} from {} to {}", size, from + 1, to + 1));
+    }
+    lines.join("\n")
+}
+
+/// A day 6 signal together with where its markers were planted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signal {
+    pub signal: String,
+    /// Characters read up to the end of the first start-of-packet marker.
+    pub start_of_packet: usize,
+    /// Characters read up to the end of the first start-of-message marker.
+    pub start_of_message: usize,
+}
+
+/// A signal of `len` characters, or a few more if `len` is too short to plant both
+/// markers. Before the start-of-packet marker only three letters are used, and before the
+/// start-of-message marker only thirteen, so neither marker can show up early. Each marker
+/// starts with the letter just before it, so no window straddling the boundary is a marker
+/// either.
+pub fn day06(rng: &mut Rng, len: usize) -> Signal {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+    let few = &letters[..13];
+
+    let quarter = (len / 4).max(1);
+    let mut signal: Vec<char> = (0..rng.between(1, quarter))
+        .map(|_| rng.pick(&few[..3]))
+        .collect();
+
+    let last = *signal.last().unwrap();
+    signal.push(last);
+    signal.extend(few.iter().filter(|c| **c != last).take(3));
+    let start_of_packet = signal.len();
+
+    signal.extend((0..rng.between(1, quarter)).map(|_| rng.pick(few)));
+    let last = *signal.last().unwrap();
+    signal.push(last);
+    let mut others: Vec<char> = letters.iter().copied().filter(|c| *c != last).collect();
+    rng.shuffle(&mut others);
+    signal.extend(&others[..13]);
+    let start_of_message = signal.len();
+
+    while signal.len() < len {
+        signal.push(rng.pick(&letters));
+    }
+
+    Signal {
+        signal: signal.into_iter().collect(),
+        start_of_packet,
+        start_of_message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{self, ParseMode};
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for day in 1..=6 {
+            assert_eq!(generate(day, 7, 50), generate(day, 7, 50));
+            assert_ne!(generate(day, 7, 50), generate(day, 8, 50));
+        }
+        assert_eq!(generate(7, 7, 50), None);
+    }
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        // Days scaffolded since the generators were written have none yet.
+        for day in crate::days() {
+            for seed in 0..20 {
+                let Some(input) = generate(day.number, seed, 300) else {
+                    continue;
+                };
+                let answers = solution::solve(day.solution, &input, None, ParseMode::Strict)
+                    .unwrap_or_else(|e| panic!("day {} seed {}: {}", day.number, seed, e));
+                assert!(answers.part1.unwrap().is_ok(), "day {}", day.number);
+                assert!(answers.part2.unwrap().is_ok(), "day {}", day.number);
+            }
+        }
+    }
+
+    #[test]
+    fn test_day03_groups_share_one_badge() {
+        let input = day03(&mut Rng::new(3), 10);
+        let lines: Vec<_> = input.lines().collect();
+        assert_eq!(lines.len(), 12);
+        for group in lines.chunks(3) {
+            let common = group[0]
+                .chars()
+                .filter(|c| group[1].contains(*c) && group[2].contains(*c))
+                .collect::<std::collections::BTreeSet<_>>();
+            assert_eq!(common.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_day06_finds_planted_markers() {
+        for seed in 0..200 {
+            let planted = day06(&mut Rng::new(seed), 100);
+            assert_eq!(planted.signal.len(), 100);
+            assert_eq!(
+                crate::day06::parse_marker_or_consume_char(&planted.signal),
+                Some(planted.start_of_packet),
+                "seed {}",
+                seed
+            );
+            assert_eq!(
+                crate::day06::parse_message_marker_or_consume_char(&planted.signal),
+                Some(planted.start_of_message),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_day05_stress_moves() {
+        let input = day05(&mut Rng::new(5), 20_000);
+        let day = crate::find_day("5").unwrap();
+        let answers = solution::solve(day.solution, &input, None, ParseMode::Strict).unwrap();
+        assert_eq!(
+            answers.part1.unwrap().unwrap().len(),
+            answers.part2.unwrap().unwrap().len()
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+
+/// Directory searched for `dayNN.txt` when no input is given, unless `AOC_INPUT_DIR` is set.
+pub const INPUT_DIR: &str = "inputs";
+
+pub fn default_path(day: u8) -> PathBuf {
+    let dir = std::env::var("AOC_INPUT_DIR").unwrap_or_else(|_| INPUT_DIR.to_string());
+    PathBuf::from(dir).join(format!("day{:02}.txt", day))
+}
+
+/// Name an input is recorded under in the answers file: `data` for the default input,
+/// `stdin` for `-`, otherwise the file stem.
+pub fn name(path: Option<&str>) -> String {
+    match path {
+        None => "data".to_string(),
+        Some("-") => "stdin".to_string(),
+        Some(path) => Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string()),
+    }
+}
+
+/// Reads the puzzle input from `path`, from stdin when `path` is `-`, or from the
+/// default inputs directory when no path is given. The text is [`normalize`]d.
+pub fn load(day: u8, path: Option<&str>) -> Result<String, AocError> {
+    let input = match path {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+        Some(path) => read(PathBuf::from(path))?,
+        None => read(default_path(day))?,
+    };
+    Ok(normalize(&input))
+}
+
+/// Strips a UTF-8 BOM, converts CRLF line endings to LF and trims trailing whitespace,
+/// so parsers only ever see `\n`-separated lines with no trailing blank line.
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    input.replace("\r\n", "\n").trim_end().to_string()
+}
+
+fn read(path: PathBuf) -> io::Result<String> {
+    std::fs::read_to_string(&path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize("\u{feff}1000\r\n2000\r\n\r\n3000\r\n\r\n"),
+            "1000\n2000\n\n3000"
+        );
+        assert_eq!(normalize("1000\n2000"), "1000\n2000");
+    }
+
+    #[test]
+    fn test_normalize_keeps_leading_whitespace() {
+        assert_eq!(
+            normalize("    [D]    \r\n[N] [C]    \n"),
+            "    [D]    \n[N] [C]"
+        );
+    }
+}
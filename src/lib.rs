@@ -0,0 +1,74 @@
+pub mod answers;
+pub mod bench;
+pub mod cases;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod diagnostics;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod input;
+#[cfg(test)]
+mod mock_server;
+pub mod readme;
+pub mod record;
+pub mod registry;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod watch;
+
+use std::sync::OnceLock;
+
+use solution::DynSolution;
+
+/// A day registered with `#[aoc(day = N)]` on its [`solution::Solution`] impl.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub solution: &'static dyn DynSolution,
+}
+
+inventory::collect!(Day);
+
+/// Every registered day, in order.
+pub fn days() -> &'static [Day] {
+    static ALL: OnceLock<Vec<Day>> = OnceLock::new();
+    ALL.get_or_init(|| {
+        let mut days: Vec<Day> = inventory::iter::<Day>().copied().collect();
+        days.sort_by_key(|d| d.number);
+        days
+    })
+}
+
+/// Looks up a day by number, accepting both `5` and `day05`.
+pub fn find_day(day: &str) -> Option<&'static Day> {
+    let number = puzzle_number(day)?;
+    days().iter().find(|d| d.number == number)
+}
+
+/// Resolves a day argument to puzzle numbers, whether or not the days are implemented yet:
+/// a single day from 1 to 25, or all of them for `all`.
+pub fn select_puzzles(day: &str) -> Option<Vec<u8>> {
+    if day == "all" {
+        return Some((1..=25).collect());
+    }
+    puzzle_number(day).map(|n| vec![n])
+}
+
+fn puzzle_number(day: &str) -> Option<u8> {
+    let number: u8 = day.trim_start_matches("day").parse().ok()?;
+    (1..=25).contains(&number).then_some(number)
+}
+
+/// Resolves a day argument: a single day, or every day for `all`.
+pub fn select_days(day: &str) -> Option<Vec<&'static Day>> {
+    if day == "all" {
+        return Some(days().iter().collect());
+    }
+    find_day(day).map(|d| vec![d])
+}
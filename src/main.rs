@@ -1,3 +1,509 @@
-fn main() {
-    println!("Hello, world!");
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use advent_of_code_rust::answers::{self, AnswerDb, Verdict, ANSWERS_FILE};
+use advent_of_code_rust::bench::{self, Report};
+use advent_of_code_rust::error::AocError;
+use advent_of_code_rust::fetch::{self, Fetched};
+use advent_of_code_rust::generate;
+use advent_of_code_rust::readme;
+use advent_of_code_rust::record::{self, RunRecord, Status};
+use advent_of_code_rust::solution::{self, ParseMode};
+use advent_of_code_rust::submit::{self, Outcome};
+use advent_of_code_rust::{
+    days, find_day, input, registry, scaffold, select_days, select_puzzles, watch, Day,
+};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one day (e.g. `5`), or every day (`all`) in parallel with a summary table
+    Run {
+        day: String,
+        /// Only run the given part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Input file, or `-` for stdin (defaults to `inputs/dayNN.txt`)
+        #[arg(long)]
+        input: Option<String>,
+        /// Use an alternative implementation registered under this name
+        #[arg(long, requires = "part")]
+        solver: Option<String>,
+        /// Ignore input left over after parsing instead of failing
+        #[arg(long)]
+        lenient: bool,
+        /// Print answers as text, or as one JSON record per day and part
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Time parsing and each part separately
+    Bench {
+        #[arg(default_value = "all")]
+        day: String,
+        /// How many times each stage is run
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+        /// Also write the results to this file as JSON
+        #[arg(long)]
+        json: Option<PathBuf>,
+        /// Free-form label stored in the JSON export, e.g. a commit hash
+        #[arg(long)]
+        label: Option<String>,
+        /// Benchmark a generated input of this many records instead of the puzzle input
+        #[arg(long)]
+        synthetic: Option<usize>,
+        /// Seed for --synthetic
+        #[arg(long, default_value_t = 0, requires = "synthetic")]
+        seed: u64,
+    },
+    /// Rerun solved days and compare their answers with answers.json
+    Verify {
+        #[arg(default_value = "all")]
+        day: String,
+        /// Store answers that are not recorded yet (never overwrites changed ones)
+        #[arg(long)]
+        record: bool,
+    },
+    /// Download missing puzzle inputs into `inputs/`, also for days not implemented yet
+    Fetch {
+        #[arg(default_value = "all")]
+        day: String,
+    },
+    /// Solve one part and submit the answer to the site
+    Submit {
+        day: String,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+    },
+    /// Rebuild, test and rerun a day whenever its source or input changes
+    Watch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Generate the solver, sample and input files for a new day
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Regenerate the progress table in README.md from the registry and answers.json
+    Readme,
+    /// List the implemented days
+    List,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            solver,
+            input,
+            lenient,
+            format,
+        } => {
+            let mode = if lenient {
+                ParseMode::Lenient
+            } else {
+                ParseMode::Strict
+            };
+            run(
+                &day,
+                part,
+                solver.as_deref(),
+                input.as_deref(),
+                mode,
+                format,
+            )
+        }
+        Command::Bench {
+            day,
+            iterations,
+            json,
+            label,
+            synthetic,
+            seed,
+        } => run_bench(
+            &day,
+            iterations,
+            json,
+            label,
+            synthetic.map(|size| (size, seed)),
+        ),
+        Command::Verify { day, record } => verify(&day, record),
+        Command::Fetch { day } => fetch_inputs(&day),
+        Command::Submit { day, part } => submit_answer(&day, part),
+        Command::Watch { day } => match watch::watch(Path::new("."), day) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Command::New { day } => match scaffold::new_day(Path::new("."), day) {
+            Ok(files) => {
+                for file in files {
+                    println!("wrote {}", file.display());
+                }
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                ExitCode::FAILURE
+            }
+        },
+        Command::Readme => update_readme(),
+        Command::List => {
+            for d in days() {
+                let alternatives: Vec<_> = (1..=2)
+                    .flat_map(|part| {
+                        registry::solvers(d.number, part)
+                            .into_iter()
+                            .map(move |s| format!("part {}: {}", part, s.name))
+                    })
+                    .collect();
+                if alternatives.is_empty() {
+                    println!("day {:02}", d.number);
+                } else {
+                    println!("day {:02} ({})", d.number, alternatives.join(", "));
+                }
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+fn days_or_exit(day: &str) -> Result<Vec<&'static Day>, ExitCode> {
+    select_days(day).ok_or_else(|| {
+        eprintln!("unknown day: {}", day);
+        ExitCode::FAILURE
+    })
+}
+
+fn run(
+    day: &str,
+    part: Option<u8>,
+    solver: Option<&str>,
+    path: Option<&str>,
+    mode: ParseMode,
+    format: Format,
+) -> ExitCode {
+    let days = match days_or_exit(day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+    if days.len() > 1 && (path.is_some() || solver.is_some()) {
+        eprintln!("--input and --solver can only be used with a single day");
+        return ExitCode::FAILURE;
+    }
+
+    let ok = match (format, days.as_slice()) {
+        (Format::Text, [d]) => run_day(d, part, solver, path, mode),
+        (Format::Json, [d]) => print_records(&record::run_day(d, part, solver, path, mode)),
+        (format, days) => {
+            let records = record::run_all(days, part, mode);
+            match format {
+                Format::Text => {
+                    println!("{}", record::format_summary(&records));
+                    records.iter().all(|r| r.status == Status::Ok)
+                }
+                Format::Json => print_records(&records),
+            }
+        }
+    };
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs a single day and prints its answers, returning whether everything succeeded.
+fn run_day(
+    day: &Day,
+    part: Option<u8>,
+    solver: Option<&str>,
+    path: Option<&str>,
+    mode: ParseMode,
+) -> bool {
+    let answers = match input::load(day.number, path)
+        .and_then(|input| record::solve(day, &input, part, solver, mode))
+    {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("day {:02}: {}", day.number, e);
+            return false;
+        }
+    };
+
+    let mut ok = true;
+    for (n, answer) in [(1, answers.part1), (2, answers.part2)] {
+        match answer {
+            Some(Ok(answer)) => println!("day {:02} part {}: {}", day.number, n, answer),
+            Some(Err(e)) => {
+                eprintln!("day {:02} part {}: {}", day.number, n, e);
+                ok = false;
+            }
+            None => {}
+        }
+    }
+    ok
+}
+
+/// Prints one JSON record per line, returning whether every part succeeded.
+fn print_records(records: &[RunRecord]) -> bool {
+    let mut ok = true;
+    for r in records {
+        ok &= r.status == Status::Ok;
+        match serde_json::to_string(r) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("day {:02} part {}: {}", r.day, r.part, e);
+                ok = false;
+            }
+        }
+    }
+    ok
+}
+
+fn run_bench(
+    day: &str,
+    iterations: usize,
+    json: Option<PathBuf>,
+    label: Option<String>,
+    synthetic: Option<(usize, u64)>,
+) -> ExitCode {
+    let days = match days_or_exit(day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+
+    let mut results = vec![];
+    for d in days {
+        let input = match synthetic {
+            Some((size, seed)) => generate::generate(d.number, seed, size)
+                .ok_or_else(|| AocError::NoAnswer("no generator for this day".to_string())),
+            None => input::load(d.number, None),
+        };
+        match input.and_then(|input| bench::bench_day(d, &input, iterations)) {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                eprintln!("day {:02}: {}", d.number, e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    println!("{}", bench::format_table(&results));
+
+    if let Some(path) = json {
+        let report = Report {
+            label,
+            days: results,
+        };
+        let written = serde_json::to_string_pretty(&report)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            eprintln!("{}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn fetch_inputs(day: &str) -> ExitCode {
+    // Inputs can be fetched before the day is scaffolded.
+    let Some(days) = select_puzzles(day) else {
+        eprintln!("unknown day: {}", day);
+        return ExitCode::FAILURE;
+    };
+    let client = match fetch::Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut ok = true;
+    for day in days {
+        match fetch::fetch(&client, day) {
+            Ok(Fetched::Cached(path)) => {
+                println!("day {:02}: cached ({})", day, path.display())
+            }
+            Ok(Fetched::Downloaded(path)) => {
+                println!("day {:02}: downloaded ({})", day, path.display())
+            }
+            Err(e) => {
+                eprintln!("day {:02}: {}", day, e);
+                ok = false;
+            }
+        }
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn submit_answer(day: &str, part: u8) -> ExitCode {
+    let Some(d) = find_day(day) else {
+        eprintln!("unknown day: {}", day);
+        return ExitCode::FAILURE;
+    };
+
+    let answer = input::load(d.number, None)
+        .and_then(|input| solution::solve(d.solution, &input, Some(part), ParseMode::Strict))
+        .and_then(|answers| {
+            let answer = if part == 1 {
+                answers.part1
+            } else {
+                answers.part2
+            };
+            answer.expect("the requested part is solved")
+        });
+    let answer = match answer {
+        Ok(answer) => answer,
+        Err(e) => {
+            eprintln!("day {:02} part {}: {}", d.number, part, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let path = Path::new(ANSWERS_FILE);
+    let outcome = AnswerDb::load(path).and_then(|mut db| {
+        let client = fetch::Client::from_env()?;
+        let outcome = submit::submit(&client, &mut db, d.number, part, &answer)?;
+        db.save(path)?;
+        Ok(outcome)
+    });
+
+    let prefix = format!("day {:02} part {}: {}", d.number, part, answer);
+    match outcome {
+        Ok(Outcome::Correct) => println!("{} is correct", prefix),
+        Ok(Outcome::AlreadyCorrect) => println!("{} is already recorded as correct", prefix),
+        Ok(Outcome::Incorrect(hint)) => {
+            match hint {
+                Some(hint) => println!("{} is wrong ({})", prefix, hint),
+                None => println!("{} is wrong", prefix),
+            }
+            return ExitCode::FAILURE;
+        }
+        Ok(Outcome::PreviouslyRejected(_)) => {
+            println!("{} was already rejected, not submitting it again", prefix);
+            return ExitCode::FAILURE;
+        }
+        Ok(Outcome::RateLimited(wait)) => {
+            match wait {
+                Some(wait) => println!("{}: rate limited, try again in {:?}", prefix, wait),
+                None => println!("{}: rate limited, try again later", prefix),
+            }
+            return ExitCode::FAILURE;
+        }
+        Ok(Outcome::WrongLevel) => {
+            println!("{}: this part is already solved or not unlocked", prefix);
+            return ExitCode::FAILURE;
+        }
+        Err(e) => {
+            eprintln!("{}: {}", prefix, e);
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn update_readme() -> ExitCode {
+    let implemented: Vec<_> = days().iter().map(|d| d.number).collect();
+    match readme::regenerate(Path::new("."), &implemented) {
+        Ok(stars) => {
+            println!("updated README.md ({} stars)", stars);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("README.md: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn verify(day: &str, record: bool) -> ExitCode {
+    let days = match days_or_exit(day) {
+        Ok(days) => days,
+        Err(code) => return code,
+    };
+    let path = Path::new(ANSWERS_FILE);
+    let mut db = match AnswerDb::load(path) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let input_name = input::name(None);
+    let mut ok = true;
+    let mut recorded = 0;
+    for d in days {
+        let verdicts = match input::load(d.number, None)
+            .and_then(|input| answers::verify_day(&db, d, &input_name, &input))
+        {
+            Ok(verdicts) => verdicts,
+            Err(e) => {
+                eprintln!("day {:02}: {}", d.number, e);
+                ok = false;
+                continue;
+            }
+        };
+
+        for (part, verdict) in (1..=2).zip(verdicts) {
+            ok &= verdict.is_ok();
+            match verdict {
+                Verdict::Match(answer) => {
+                    println!("day {:02} part {}: ok ({})", d.number, part, answer)
+                }
+                Verdict::Changed { expected, actual } => println!(
+                    "day {:02} part {}: CHANGED, expected {} but got {}",
+                    d.number, part, expected, actual
+                ),
+                Verdict::New(answer) => {
+                    println!("day {:02} part {}: new ({})", d.number, part, answer);
+                    if record {
+                        db.record(d.number, &input_name, part, answer);
+                        recorded += 1;
+                    }
+                }
+                Verdict::Failed(e) => println!("day {:02} part {}: FAILED, {}", d.number, part, e),
+            }
+        }
+    }
+
+    if recorded > 0 {
+        if let Err(e) = db.save(path) {
+            eprintln!("{}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+        println!("recorded {} new answers in {}", recorded, path.display());
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
@@ -0,0 +1,133 @@
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+use crate::answers::{AnswerDb, ANSWERS_FILE};
+use crate::error::AocError;
+
+/// The generated section sits between these markers; the rest of the README is left alone.
+pub const START: &str = "<!-- progress:start -->";
+pub const END: &str = "<!-- progress:end -->";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayProgress {
+    pub day: u8,
+    /// The day is in the registry.
+    pub implemented: bool,
+    /// Which parts have an answer the site accepted in the answers file.
+    pub solved: [bool; 2],
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> usize {
+        self.solved.iter().filter(|s| **s).count()
+    }
+}
+
+/// Progress for all 25 days, from the answers recorded for the `data` input. Only what is
+/// checked in goes into the table, so regenerating it on another machine changes nothing.
+pub fn progress(db: &AnswerDb, implemented: &[u8]) -> Vec<DayProgress> {
+    (1..=25)
+        .map(|day| DayProgress {
+            day,
+            implemented: implemented.contains(&day),
+            solved: [1, 2].map(|part| db.accepted(day, "data", part).is_some()),
+        })
+        .collect()
+}
+
+/// Markdown table of every day, followed by the star total.
+pub fn render(progress: &[DayProgress]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "| Day | Part 1 | Part 2 | Stars |");
+    let _ = writeln!(out, "| --: | :----: | :----: | :---- |");
+    for p in progress {
+        let [part1, part2] = p.solved.map(|solved| if solved { "x" } else { " " });
+        let day = if p.implemented {
+            format!("[{}](src/day{:02}/mod.rs)", p.day, p.day)
+        } else {
+            p.day.to_string()
+        };
+        let _ = writeln!(
+            out,
+            "| {} | [{}] | [{}] | {} |",
+            day,
+            part1,
+            part2,
+            "★".repeat(p.stars())
+        );
+    }
+
+    let stars: usize = progress.iter().map(DayProgress::stars).sum();
+    let _ = write!(out, "\n**{} / {} stars**", stars, progress.len() * 2);
+    out
+}
+
+/// Replaces whatever is between the progress markers in `readme` with `section`.
+pub fn update(readme: &str, section: &str) -> Result<String, AocError> {
+    let start = readme.find(START);
+    let end = readme.find(END);
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => Ok(format!(
+            "{}{}\n{}\n{}",
+            &readme[..start],
+            START,
+            section,
+            &readme[end..]
+        )),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("README.md needs {} and {} markers", START, END),
+        )
+        .into()),
+    }
+}
+
+/// Rewrites the progress section of `root/README.md` from `root/answers.json`, and returns
+/// the number of stars.
+pub fn regenerate(root: &Path, implemented: &[u8]) -> Result<usize, AocError> {
+    let db = AnswerDb::load(&root.join(ANSWERS_FILE))?;
+    let progress = progress(&db, implemented);
+
+    let path = root.join("README.md");
+    let readme = update(&std::fs::read_to_string(&path)?, &render(&progress))?;
+    std::fs::write(&path, readme)?;
+    Ok(progress.iter().map(DayProgress::stars).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let mut db = AnswerDb::default();
+        db.accept(1, "data", 1, "24000".to_string());
+        db.accept(1, "data", 2, "45000".to_string());
+        db.accept(2, "data", 1, "15".to_string());
+        db.record(2, "data", 2, "12".to_string());
+        db.accept(3, "sample", 1, "157".to_string());
+
+        let progress = progress(&db, &[1, 2, 3]);
+        assert_eq!(progress.len(), 25);
+        assert_eq!(progress[0].stars(), 2);
+        assert_eq!(progress[1].solved, [true, false]);
+        assert_eq!(progress[2].stars(), 0);
+        assert!(!progress[3].implemented);
+
+        let table = render(&progress);
+        assert!(table.contains("| [1](src/day01/mod.rs) | [x] | [x] | ★★ |\n"));
+        assert!(table.contains("| 25 | [ ] | [ ] |  |\n"));
+        assert!(table.ends_with("**3 / 50 stars**"));
+    }
+
+    #[test]
+    fn test_update_keeps_surrounding_text() {
+        let readme = format!("# AoC\n\n{}\nold\n{}\n\nfooter\n", START, END);
+        assert_eq!(
+            update(&readme, "new").unwrap(),
+            format!("# AoC\n\n{}\nnew\n{}\n\nfooter\n", START, END)
+        );
+        assert!(update("# AoC\n", "new").is_err());
+    }
+}
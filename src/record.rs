@@ -0,0 +1,366 @@
+use std::fmt::Write;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::AocError;
+use crate::input;
+use crate::registry;
+use crate::solution::{self, Answers, ParseMode};
+use crate::Day;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    Error,
+}
+
+/// Result of running one part of one day, as printed by `aoc run --format json`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RunRecord {
+    pub day: u8,
+    pub part: u8,
+    /// Input name, as used in the answers file (`data`, `stdin` or the file stem).
+    pub input: String,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    /// Time spent parsing; missing when the input could not be loaded or parsed.
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+}
+
+impl RunRecord {
+    fn failed(day: u8, part: u8, input: &str, error: String) -> Self {
+        Self {
+            day,
+            part,
+            input: input.to_string(),
+            status: Status::Error,
+            answer: None,
+            error: Some(error),
+            parse_ns: None,
+            solve_ns: None,
+        }
+    }
+
+    fn solved(
+        day: u8,
+        part: u8,
+        input: &str,
+        answer: Result<String, String>,
+        parse_time: Duration,
+        solve_time: Duration,
+    ) -> Self {
+        let mut record = match answer {
+            Ok(answer) => RunRecord {
+                status: Status::Ok,
+                answer: Some(answer),
+                error: None,
+                ..RunRecord::failed(day, part, input, String::new())
+            },
+            Err(error) => RunRecord::failed(day, part, input, error),
+        };
+        record.parse_ns = Some(parse_time.as_nanos() as u64);
+        record.solve_ns = Some(solve_time.as_nanos() as u64);
+        record
+    }
+}
+
+fn parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
+
+/// Loads the input for `day` and runs the requested part (or both), producing one record
+/// per part. Loading and parsing failures are reported on every requested part.
+///
+/// `solver` picks a named alternative registered with `#[aoc]`; it needs `part`.
+pub fn run_day(
+    day: &Day,
+    part: Option<u8>,
+    solver: Option<&str>,
+    path: Option<&str>,
+    mode: ParseMode,
+) -> Vec<RunRecord> {
+    let input_name = input::name(path);
+    let answers = match input::load(day.number, path)
+        .and_then(|input| solve(day, &input, part, solver, mode))
+    {
+        Ok(answers) => answers,
+        Err(e) => {
+            return parts(part)
+                .into_iter()
+                .map(|part| RunRecord::failed(day.number, part, &input_name, e.to_string()))
+                .collect()
+        }
+    };
+
+    [
+        (1, answers.part1, answers.part1_time),
+        (2, answers.part2, answers.part2_time),
+    ]
+    .into_iter()
+    .filter_map(|(part, answer, time)| {
+        let answer = answer?.map_err(|e| e.to_string());
+        Some(RunRecord::solved(
+            day.number,
+            part,
+            &input_name,
+            answer,
+            answers.parse_time,
+            time,
+        ))
+    })
+    .collect()
+}
+
+/// Solves with the default solvers, or with the named one for a single part.
+pub fn solve(
+    day: &Day,
+    input: &str,
+    part: Option<u8>,
+    solver: Option<&str>,
+    mode: ParseMode,
+) -> Result<Answers, AocError> {
+    match (solver, part) {
+        (Some(name), Some(part)) => registry::solve_named(day, input, part, name, mode),
+        (Some(_), None) => Err(AocError::NoAnswer(
+            "a named solver needs a part".to_string(),
+        )),
+        (None, _) => solution::solve(day.solution, input, part, mode),
+    }
+}
+
+/// Runs every day on its default input, spread over a pool of worker threads. A part that
+/// panics is reported as failed without stopping the others. Records come back in the
+/// order of `days`.
+pub fn run_all(days: &[&Day], part: Option<u8>, mode: ParseMode) -> Vec<RunRecord> {
+    let workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(days.len())
+        .max(1);
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(vec![]);
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let records = run_isolated(day, part, mode);
+                    results.lock().unwrap().push((day.number, records));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(day, _)| days.iter().position(|d| d.number == *day));
+    results
+        .into_iter()
+        .flat_map(|(_, records)| records)
+        .collect()
+}
+
+/// Runs a day for [`run_all`]. The input is parsed once and each part is caught on its own,
+/// so a panic in part 1 still lets part 2 report its answer.
+fn run_isolated(day: &Day, part: Option<u8>, mode: ParseMode) -> Vec<RunRecord> {
+    let input_name = input::name(None);
+    let parsed = catch_panic(|| {
+        let input = input::load(day.number, None)?;
+        let start = Instant::now();
+        let parsed = day.solution.parse(&input, mode)?;
+        Ok((parsed, start.elapsed()))
+    })
+    .and_then(|parsed| parsed.map_err(|e: AocError| e.to_string()));
+    let (parsed, parse_time) = match parsed {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            return parts(part)
+                .into_iter()
+                .map(|p| RunRecord::failed(day.number, p, &input_name, error.clone()))
+                .collect()
+        }
+    };
+
+    parts(part)
+        .into_iter()
+        .map(|p| {
+            let start = Instant::now();
+            let answer = catch_panic(|| match p {
+                1 => day.solution.part1(parsed.as_ref()),
+                _ => day.solution.part2(parsed.as_ref()),
+            })
+            .and_then(|answer| answer.map_err(|e| e.to_string()));
+            RunRecord::solved(
+                day.number,
+                p,
+                &input_name,
+                answer,
+                parse_time,
+                start.elapsed(),
+            )
+        })
+        .collect()
+}
+
+/// Runs `f`, turning a panic into a `panicked: ...` error message.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown cause".to_string());
+        format!("panicked: {}", message)
+    })
+}
+
+/// Table of every record: day, part, answer, time spent solving the part, and status.
+/// Error messages are listed below the table.
+pub fn format_summary(records: &[RunRecord]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<5} {:<5} {:<20} {:>12} status",
+        "day", "part", "answer", "time"
+    );
+    for r in records {
+        let time = r
+            .solve_ns
+            .map(|ns| format!("{:.2?}", Duration::from_nanos(ns)))
+            .unwrap_or_else(|| "-".to_string());
+        let status = match r.status {
+            Status::Ok => "ok",
+            Status::Error => "FAILED",
+        };
+        let _ = writeln!(
+            out,
+            "{:<5} {:<5} {:<20} {:>12} {}",
+            format!("{:02}", r.day),
+            r.part,
+            r.answer.as_deref().unwrap_or("-"),
+            time,
+            status
+        );
+    }
+
+    let failed = records.iter().filter(|r| r.status == Status::Error).count();
+    let _ = write!(
+        out,
+        "{} of {} parts ok",
+        records.len() - failed,
+        records.len()
+    );
+    for r in records {
+        if let Some(error) = &r.error {
+            let _ = write!(out, "\n\nday {:02} part {}: {}", r.day, r.part, error);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(day: u8) -> String {
+        format!(
+            "{}/src/day{:02}/sample.txt",
+            env!("CARGO_MANIFEST_DIR"),
+            day
+        )
+    }
+
+    #[test]
+    fn test_records_per_part() {
+        let day = crate::find_day("5").unwrap();
+        let records = run_day(day, None, None, Some(&sample(5)), ParseMode::Strict);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].input, "sample");
+        assert_eq!(records[0].status, Status::Ok);
+        assert_eq!(records[1].answer.as_deref(), Some("MCD"));
+        assert!(records[1].parse_ns.is_some());
+
+        let json = serde_json::to_value(&records[0]).unwrap();
+        assert_eq!(json["status"], "ok");
+        assert_eq!(json["answer"], "CMZ");
+    }
+
+    #[test]
+    fn test_load_error_is_reported_per_part() {
+        let day = crate::find_day("1").unwrap();
+        let records = run_day(
+            day,
+            Some(2),
+            None,
+            Some("no-such-file.txt"),
+            ParseMode::Strict,
+        );
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].part, 2);
+        assert_eq!(records[0].status, Status::Error);
+        assert!(records[0]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("no-such-file.txt"));
+        assert_eq!(records[0].parse_ns, None);
+    }
+
+    struct Panics;
+
+    impl crate::solution::Solution for Panics {
+        type Input = ();
+        type Output1 = u32;
+        type Output2 = u32;
+
+        fn parse(_: &str) -> crate::diagnostics::ParseResult<'_, ()> {
+            Ok(("", ()))
+        }
+
+        fn part1(_: &()) -> Result<u32, crate::error::AocError> {
+            panic!("part 1 exploded")
+        }
+
+        fn part2(_: &()) -> Result<u32, crate::error::AocError> {
+            Ok(42)
+        }
+    }
+
+    #[test]
+    fn test_run_all_isolates_panics() {
+        let panics = Day {
+            number: 6,
+            solution: &Panics,
+        };
+        let days = [
+            crate::find_day("4").unwrap(),
+            &panics,
+            crate::find_day("1").unwrap(),
+        ];
+        let records = run_all(&days, None, ParseMode::Strict);
+
+        let order: Vec<_> = records.iter().map(|r| (r.day, r.part)).collect();
+        assert_eq!(order, [(4, 1), (4, 2), (6, 1), (6, 2), (1, 1), (1, 2)]);
+        assert_eq!(records[0].status, Status::Ok);
+        assert_eq!(
+            records[2].error.as_deref(),
+            Some("panicked: part 1 exploded")
+        );
+        assert_eq!(records[3].answer.as_deref(), Some("42"));
+        assert_eq!(records[4].status, Status::Ok);
+
+        let summary = format_summary(&records);
+        assert!(summary.contains("5 of 6 parts ok"));
+        assert!(summary.contains("day 06 part 1: panicked: part 1 exploded"));
+    }
+}
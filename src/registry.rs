@@ -0,0 +1,144 @@
+//! Alternative solvers registered with `#[aoc(day = N, part = P, name = "...")]`.
+//!
+//! Each takes the parsed input of its day's `Solution` impl and can be picked instead of
+//! the day's own part with `aoc run --solver`.
+
+use std::any::{type_name, Any};
+use std::fmt::Display;
+use std::time::Instant;
+
+use crate::error::AocError;
+use crate::solution::{self, Answers, ParseMode};
+use crate::Day;
+
+/// Name of the day's own solver, used when none is asked for.
+pub const DEFAULT: &str = "default";
+
+pub type SolveFn = fn(&dyn Any) -> Result<String, AocError>;
+
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub solve: SolveFn,
+}
+
+inventory::collect!(Solver);
+
+/// Implemented by `#[aoc(day = N)]` for each day, so `#[aoc]` can check at compile time
+/// that a solver takes the input the day's parser produces.
+pub trait DayInput<const DAY: u8> {
+    type Input: 'static;
+}
+
+pub struct Days;
+
+/// Used by `#[aoc]` to erase the solver's input and output types.
+pub fn erase_solve<T: 'static, O: Display>(
+    input: &dyn Any,
+    solve: fn(&T) -> Result<O, AocError>,
+) -> Result<String, AocError> {
+    let input = input.downcast_ref().unwrap_or_else(|| {
+        panic!(
+            "solver expects {} but was given something else",
+            type_name::<T>()
+        )
+    });
+    Ok(solve(input)?.to_string())
+}
+
+/// The alternative solvers registered for one part of `day`, by name.
+pub fn solvers(day: u8, part: u8) -> Vec<&'static Solver> {
+    let mut solvers: Vec<_> = inventory::iter::<Solver>()
+        .filter(|s| s.day == day && s.part == part)
+        .collect();
+    solvers.sort_by_key(|s| s.name);
+    solvers
+}
+
+pub fn solver(day: u8, part: u8, name: &str) -> Option<&'static Solver> {
+    solvers(day, part).into_iter().find(|s| s.name == name)
+}
+
+/// Like [`solution::solve`] for a single part, but with the solver registered as `name`.
+/// [`DEFAULT`] names the day's own part.
+pub fn solve_named(
+    day: &Day,
+    input: &str,
+    part: u8,
+    name: &str,
+    mode: ParseMode,
+) -> Result<Answers, AocError> {
+    if name == DEFAULT {
+        return solution::solve(day.solution, input, Some(part), mode);
+    }
+    let solver = solver(day.number, part, name)
+        .ok_or_else(|| AocError::NoAnswer(format!("part {} has no solver named {}", part, name)))?;
+
+    let start = Instant::now();
+    let parsed = day.solution.parse(input, mode)?;
+    let mut answers = Answers {
+        parse_time: start.elapsed(),
+        ..Answers::default()
+    };
+
+    let start = Instant::now();
+    let answer = Some((solver.solve)(parsed.as_ref()));
+    if part == 1 {
+        answers.part1 = answer;
+        answers.part1_time = start.elapsed();
+    } else {
+        answers.part2 = answer;
+        answers.part2_time = start.elapsed();
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day04::{self, Range};
+    use aoc_macros::aoc;
+
+    // Solvers take a reference to the parsed input itself, hence `&Vec`.
+    #[allow(clippy::ptr_arg)]
+    #[aoc(day = 4, part = 1, name = "contains_either")]
+    fn fully_overlapped_by_containment(pairs: &Vec<(Range, Range)>) -> Result<usize, AocError> {
+        Ok(pairs
+            .iter()
+            .filter(|(a, b)| {
+                (a.start <= b.start && b.end <= a.end) || (b.start <= a.start && a.end <= b.end)
+            })
+            .count())
+    }
+
+    #[test]
+    fn test_alternative_solver() {
+        let day = crate::find_day("5").unwrap();
+        let input = include_str!("./day05/sample.txt");
+        let answers = solution::solve(day.solution, input, None, ParseMode::Strict).unwrap();
+        assert_eq!(answers.part1.unwrap().unwrap(), "CMZ");
+        assert_eq!(answers.part2.unwrap().unwrap(), "MCD");
+
+        let names: Vec<_> = solvers(5, 2).iter().map(|s| s.name).collect();
+        assert_eq!(names, ["one_at_a_time"]);
+        let answers = solve_named(day, input, 2, "one_at_a_time", ParseMode::Strict).unwrap();
+        assert_eq!(answers.part2.unwrap().unwrap(), "MCD");
+        let answers = solve_named(day, input, 2, DEFAULT, ParseMode::Strict).unwrap();
+        assert_eq!(answers.part2.unwrap().unwrap(), "MCD");
+        assert!(answers.part1.is_none());
+    }
+
+    #[test]
+    fn test_named_solver_for_trait_day() {
+        let day = crate::find_day("4").unwrap();
+        let input = include_str!("./day04/sample.txt");
+        let answers = solve_named(day, input, 1, "contains_either", ParseMode::Strict).unwrap();
+        let (_, pairs) = day04::parse_ranges(input).unwrap();
+        assert_eq!(
+            answers.part1.unwrap().unwrap(),
+            day04::get_fully_overlapped(&pairs).to_string()
+        );
+        assert!(solve_named(day, input, 2, "contains_either", ParseMode::Strict).is_err());
+    }
+}
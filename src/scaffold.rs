@@ -0,0 +1,172 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::AocError;
+use crate::readme;
+
+const MOD_TEMPLATE: &str = include_str!("../templates/mod.rs.tmpl");
+const MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.tmpl");
+
+/// Creates `src/dayNN/` from the templates with an empty sample and answers sidecar, an
+/// empty input file, registers the day in Cargo.toml and src/lib.rs, and regenerates the
+/// README progress table with the day in it. Returns the files that were touched.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, AocError> {
+    if !(1..=25).contains(&day) {
+        return Err(
+            io::Error::new(io::ErrorKind::InvalidInput, format!("no such day: {}", day)).into(),
+        );
+    }
+
+    if !root.join("Cargo.toml").exists() || !root.join("src/lib.rs").exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not the repository root", root.display()),
+        )
+        .into());
+    }
+
+    let dir = root.join(format!("src/day{:02}", day));
+    if dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dir.display()),
+        )
+        .into());
+    }
+
+    let mut touched = vec![];
+    let mut write = |path: PathBuf, contents: String| -> Result<(), AocError> {
+        fs::write(&path, contents)?;
+        touched.push(path);
+        Ok(())
+    };
+
+    fs::create_dir_all(&dir)?;
+    fs::create_dir_all(root.join("inputs"))?;
+    write(dir.join("mod.rs"), render(MOD_TEMPLATE, day))?;
+    write(dir.join("main.rs"), render(MAIN_TEMPLATE, day))?;
+    write(dir.join("sample.txt"), String::new())?;
+    write(dir.join("sample.answers.json"), "{}\n".to_string())?;
+    // The input may have been fetched before the day was scaffolded.
+    let input = root.join(format!("inputs/day{:02}.txt", day));
+    if !input.exists() {
+        write(input, String::new())?;
+    }
+
+    let manifest = root.join("Cargo.toml");
+    write(
+        manifest.clone(),
+        register_bin(&fs::read_to_string(&manifest)?, day),
+    )?;
+
+    let lib = root.join("src/lib.rs");
+    write(
+        lib.clone(),
+        register_module(&fs::read_to_string(&lib)?, day),
+    )?;
+
+    let readme = root.join("README.md");
+    if readme.exists() {
+        let mut implemented: Vec<u8> = crate::days().iter().map(|d| d.number).collect();
+        implemented.push(day);
+        readme::regenerate(root, &implemented)?;
+        touched.push(readme);
+    }
+
+    Ok(touched)
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{NN}}", &format!("{:02}", day))
+        .replace("{{N}}", &day.to_string())
+}
+
+/// Adds a `[[bin]]` block after the last per-day binary.
+fn register_bin(manifest: &str, day: u8) -> String {
+    let block = format!(
+        "\n\n[[bin]]\nname = \"day{:02}\"\npath = \"src/day{:02}/main.rs\"",
+        day, day
+    );
+    let at = last_line_end(manifest, |line| {
+        line.starts_with("path = \"src/day") && line.ends_with("/main.rs\"")
+    });
+    insert(manifest, at, &block)
+}
+
+/// Adds `pub mod dayNN;`; the day registers itself through `#[aoc]` in its module.
+fn register_module(lib: &str, day: u8) -> String {
+    let at = last_line_end(lib, |line| line.starts_with("pub mod day"));
+    insert(lib, at, &format!("\npub mod day{:02};", day))
+}
+
+/// Byte offset just before the newline of the last line matching `pred`.
+fn last_line_end(text: &str, pred: impl Fn(&str) -> bool) -> usize {
+    let mut offset = 0;
+    let mut found = text.len();
+    for line in text.split_inclusive('\n') {
+        if pred(line.trim_end()) {
+            found = offset + line.trim_end_matches('\n').len();
+        }
+        offset += line.len();
+    }
+    found
+}
+
+fn insert(text: &str, at: usize, snippet: &str) -> String {
+    format!("{}{}{}", &text[..at], snippet, &text[at..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_bin() {
+        let manifest = "[[bin]]\nname = \"day06\"\npath = \"src/day06/main.rs\"\n\n[[bin]]\nname = \"aoc\"\npath = \"src/main.rs\"\n";
+        assert_eq!(
+            register_bin(manifest, 7),
+            "[[bin]]\nname = \"day06\"\npath = \"src/day06/main.rs\"\n\n[[bin]]\nname = \"day07\"\npath = \"src/day07/main.rs\"\n\n[[bin]]\nname = \"aoc\"\npath = \"src/main.rs\"\n"
+        );
+    }
+
+    #[test]
+    fn test_register_module() {
+        let lib = "pub mod day05;\npub mod day06;\npub mod input;\n";
+        assert_eq!(
+            register_module(lib, 7),
+            "pub mod day05;\npub mod day06;\npub mod day07;\npub mod input;\n"
+        );
+    }
+
+    #[test]
+    fn test_new_day_updates_the_readme() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"aoc\"\n").unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod day06;\n").unwrap();
+        let readme = format!("# AoC\n\n{}\n{}\n", readme::START, readme::END);
+        fs::write(root.join("README.md"), readme).unwrap();
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        fs::write(root.join("inputs/day09.txt"), "fetched\n").unwrap();
+
+        let touched = new_day(&root, 9);
+        let input = fs::read_to_string(root.join("inputs/day09.txt"));
+        let readme = fs::read_to_string(root.join("README.md"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(input.unwrap(), "fetched\n");
+        assert!(touched.unwrap().contains(&root.join("README.md")));
+        let readme = readme.unwrap();
+        assert!(readme.contains("| [9](src/day09/mod.rs) | [ ] | [ ] |  |\n"));
+        assert!(readme.contains("| [6](src/day06/mod.rs) |"));
+    }
+
+    #[test]
+    fn test_new_day_refuses_existing_day() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert!(new_day(root, 1).is_err());
+        assert!(new_day(root, 26).is_err());
+    }
+}
@@ -0,0 +1,236 @@
+use std::any::Any;
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::diagnostics::{Diagnostic, ParseResult};
+use crate::error::AocError;
+
+/// A day's puzzle: the input is parsed once and both parts are solved from it.
+pub trait Solution {
+    type Input: 'static;
+    type Output1: Display;
+    type Output2: Display;
+
+    fn parse(input: &str) -> ParseResult<'_, Self::Input>;
+    fn part1(input: &Self::Input) -> Result<Self::Output1, AocError>;
+    fn part2(input: &Self::Input) -> Result<Self::Output2, AocError>;
+
+    /// Parses from a line `parse` stopped at, to find out what is wrong with it. Days whose
+    /// input has sections override this with the parser of the last one.
+    fn parse_leftover(input: &str) -> ParseResult<'_, ()> {
+        Self::parse(input).map(|(rest, _)| (rest, ()))
+    }
+}
+
+/// What to do with input left over after a day's top-level parser returns.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Anything but trailing whitespace is an error, so a malformed line can't be skipped silently.
+    #[default]
+    Strict,
+    /// Leftover input is ignored.
+    Lenient,
+}
+
+/// Runs `S::parse` over the whole input in [`ParseMode::Strict`].
+pub fn parse_all<S: Solution>(input: &str) -> Result<S::Input, AocError> {
+    parse_with::<S>(input, ParseMode::Strict)
+}
+
+pub fn parse_with<S: Solution>(input: &str, mode: ParseMode) -> Result<S::Input, AocError> {
+    parse_checked(input, mode, S::parse, S::parse_leftover)
+}
+
+/// Runs a top-level parser over the whole input, handling leftover input according to `mode`.
+pub fn parse_using<T>(
+    input: &str,
+    mode: ParseMode,
+    parser: impl Fn(&str) -> ParseResult<'_, T>,
+) -> Result<T, AocError> {
+    parse_checked(input, mode, &parser, &parser)
+}
+
+fn parse_checked<T, U>(
+    input: &str,
+    mode: ParseMode,
+    parser: impl Fn(&str) -> ParseResult<'_, T>,
+    leftover: impl Fn(&str) -> ParseResult<'_, U>,
+) -> Result<T, AocError> {
+    let (rest, parsed) = parser(input).map_err(|e| AocError::from_nom(input, e))?;
+    if mode == ParseMode::Strict && !rest.trim().is_empty() {
+        return Err(AocError::TrailingInput(trailing_input(
+            input, rest, leftover,
+        )));
+    }
+    Ok(parsed)
+}
+
+/// Explains why parsing stopped at `rest`. Parsers of repeated lines give up on a malformed
+/// line without saying why, so `parser` is run again from that line, where it fails with the
+/// column and expectation it backed off from.
+fn trailing_input<T>(
+    input: &str,
+    rest: &str,
+    parser: impl Fn(&str) -> ParseResult<'_, T>,
+) -> Diagnostic {
+    let mut offset = input.len() - rest.len();
+    loop {
+        // Point at the first unparsed line rather than the newline that separated it.
+        let start = input.len() - input[offset..].trim_start().len();
+        match parser(&input[start..]) {
+            Err(e) => {
+                let found = Diagnostic::from_nom(&input[start..], &e);
+                return Diagnostic::at(input, start + found.offset, found.expected);
+            }
+            Ok((more, _)) if !more.trim().is_empty() && input.len() - more.len() > start => {
+                offset = input.len() - more.len();
+            }
+            // The line parses on its own, so it is the blank line before it that is wrong.
+            Ok(_) => {
+                let blank = input[..start.saturating_sub(1)]
+                    .rfind('\n')
+                    .map_or(0, |i| i + 1);
+                return Diagnostic::at(input, blank, Some("unexpected blank line".to_string()));
+            }
+        }
+    }
+}
+
+/// Type-erased [`Solution`], so days with different input types can live in one list.
+pub trait DynSolution: Sync {
+    fn parse(&self, input: &str, mode: ParseMode) -> Result<Box<dyn Any>, AocError>;
+    fn part1(&self, input: &dyn Any) -> Result<String, AocError>;
+    fn part2(&self, input: &dyn Any) -> Result<String, AocError>;
+}
+
+impl<S: Solution + Sync> DynSolution for S {
+    fn parse(&self, input: &str, mode: ParseMode) -> Result<Box<dyn Any>, AocError> {
+        let parsed = parse_with::<S>(input, mode)?;
+        Ok(Box::new(parsed))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Result<String, AocError> {
+        let answer = <S as Solution>::part1(downcast::<S>(input))?;
+        Ok(answer.to_string())
+    }
+
+    fn part2(&self, input: &dyn Any) -> Result<String, AocError> {
+        let answer = <S as Solution>::part2(downcast::<S>(input))?;
+        Ok(answer.to_string())
+    }
+}
+
+fn downcast<S: Solution>(input: &dyn Any) -> &S::Input {
+    input
+        .downcast_ref()
+        .expect("input was not produced by the same solution's parser")
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    pub part1: Option<Result<String, AocError>>,
+    pub part2: Option<Result<String, AocError>>,
+    pub parse_time: Duration,
+    /// Zero when the part was not run.
+    pub part1_time: Duration,
+    pub part2_time: Duration,
+}
+
+/// Parses `input` and solves the requested part, or both when `part` is `None`.
+pub fn solve(
+    solution: &dyn DynSolution,
+    input: &str,
+    part: Option<u8>,
+    mode: ParseMode,
+) -> Result<Answers, AocError> {
+    let start = Instant::now();
+    let parsed = solution.parse(input, mode)?;
+    let mut answers = Answers {
+        parse_time: start.elapsed(),
+        ..Answers::default()
+    };
+
+    if part != Some(2) {
+        let start = Instant::now();
+        answers.part1 = Some(solution.part1(parsed.as_ref()));
+        answers.part1_time = start.elapsed();
+    }
+    if part != Some(1) {
+        let start = Instant::now();
+        answers.part2 = Some(solution.part2(parsed.as_ref()));
+        answers.part2_time = start.elapsed();
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day01::Day01;
+    use crate::day02::Day02;
+    use crate::day03::Day03;
+    use crate::day04::Day04;
+    use crate::day05::Day05;
+
+    #[test]
+    fn test_strict_rejects_malformed_line() {
+        let input = "2-4,6-8\n2-3,4-5\n5-7;7-9\n2-8,3-7\n";
+        match parse_all::<Day04>(input) {
+            Err(AocError::TrailingInput(d)) => {
+                assert_eq!(d.line, 3);
+                assert_eq!(d.column, 4);
+                assert_eq!(d.source_line, "5-7;7-9");
+                assert_eq!(d.expected.as_deref(), Some("expected ','"));
+            }
+            other => panic!("expected trailing input error, got {:?}", other.map(|_| ())),
+        }
+
+        assert!(parse_all::<Day03>("vJrwpWtwJgWrhcsFMMfFFhFp\n--\nPmmdzqPrVvPwwTWBwg").is_err());
+    }
+
+    fn trailing(result: Result<impl Sized, AocError>) -> (usize, usize, String) {
+        match result {
+            Err(AocError::TrailingInput(d)) => (d.line, d.column, d.expected.unwrap()),
+            Err(e) => panic!("expected trailing input error, got {:?}", e),
+            Ok(_) => panic!("expected trailing input error"),
+        }
+    }
+
+    #[test]
+    fn test_trailing_input_points_at_the_failure() {
+        assert_eq!(
+            trailing(parse_all::<Day02>("A Y\nC Q\nB X")),
+            (2, 3, "expected 'X', 'Y' or 'Z'".to_string())
+        );
+        assert_eq!(
+            trailing(parse_all::<Day02>("A Y\nB X\n\nC Z")),
+            (3, 1, "unexpected blank line".to_string())
+        );
+        // Elves are separated by one blank line, so it is the second one that is wrong.
+        assert_eq!(
+            trailing(parse_all::<Day01>("1000\n\n\n2000")),
+            (3, 1, "unexpected blank line".to_string())
+        );
+        assert_eq!(
+            trailing(parse_all::<Day01>("1000\n  \n2000")),
+            (2, 1, "unexpected blank line".to_string())
+        );
+
+        let sample = include_str!("./day05/sample.txt");
+        let input = sample.replacen("move 3 from 1 to 3", "move 3 frm 1 to 3", 1);
+        let (line, column, expected) = trailing(parse_all::<Day05>(&input));
+        assert_eq!((line, column), (7, 7));
+        assert!(expected.contains("from"), "{}", expected);
+    }
+
+    #[test]
+    fn test_strict_allows_trailing_whitespace() {
+        assert!(parse_all::<Day04>("2-4,6-8\n2-3,4-5\n\n").is_ok());
+    }
+
+    #[test]
+    fn test_lenient_ignores_leftover() {
+        let ranges = parse_with::<Day04>("2-4,6-8\n5-7;7-9", ParseMode::Lenient).unwrap();
+        assert_eq!(ranges.len(), 1);
+    }
+}
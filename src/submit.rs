@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use crate::answers::{AnswerDb, Hint};
+use crate::error::AocError;
+use crate::fetch::Client;
+
+/// Input name submissions are recorded under; the site only knows about the real input.
+const INPUT: &str = "data";
+
+/// What happened to a submitted answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Correct,
+    Incorrect(Option<Hint>),
+    /// The site wants us to wait before answering again, for the given time if it said so.
+    RateLimited(Option<Duration>),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+    /// Not sent: the site already accepted this answer.
+    AlreadyCorrect,
+    /// Not sent: the site rejected this answer before.
+    PreviouslyRejected(Option<Hint>),
+}
+
+/// Reads the outcome from the page returned after posting an answer.
+pub fn parse_response(page: &str) -> Result<Outcome, AocError> {
+    let text = article_text(page);
+
+    if text.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Ok(Outcome::Incorrect(hint))
+    } else if text.contains("You gave an answer too recently") {
+        Ok(Outcome::RateLimited(wait_time(&text)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Ok(Outcome::WrongLevel)
+    } else {
+        let excerpt: String = text.chars().take(200).collect();
+        Err(AocError::Http(format!(
+            "unrecognized response: {}",
+            excerpt
+        )))
+    }
+}
+
+/// The text of the page's `<article>` (or the whole page), with tags removed.
+fn article_text(page: &str) -> String {
+    let article = page
+        .find("<article")
+        .and_then(|start| {
+            page[start..]
+                .find("</article>")
+                .map(|end| &page[start..start + end])
+        })
+        .unwrap_or(page);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses "You have 1m 5s left to wait".
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let (n, unit) = amount.split_at(amount.find(|c: char| !c.is_ascii_digit())?);
+        let n: u64 = n.parse().ok()?;
+        seconds += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Submits `answer` for one part of `day` unless the answers database already knows the
+/// outcome, and records what the site said. The caller saves `db`.
+pub fn submit(
+    client: &Client,
+    db: &mut AnswerDb,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Outcome, AocError> {
+    if db.accepted(day, INPUT, part) == Some(answer) {
+        return Ok(Outcome::AlreadyCorrect);
+    }
+    if let Some(rejected) = db
+        .rejected(day, INPUT, part)
+        .iter()
+        .find(|r| r.answer == answer)
+    {
+        return Ok(Outcome::PreviouslyRejected(rejected.hint));
+    }
+
+    let outcome = parse_response(&client.post_answer(day, part, answer)?)?;
+    match outcome {
+        Outcome::Correct => db.accept(day, INPUT, part, answer.to_string()),
+        Outcome::Incorrect(hint) => db.reject(day, INPUT, part, answer.to_string(), hint),
+        _ => {}
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::RateLimiter;
+    use crate::mock_server::MockServer;
+
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer to collecting enough star fruit.</p></article></main>";
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.  Please wait one minute before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article></main>";
+    const TOO_RECENT: &str = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article></main>";
+    const WRONG_LEVEL: &str = "<main><article><p>You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/2022/day/1\">Return to Day 1</a>]</p></article></main>";
+
+    fn client(url: &str) -> Client {
+        let stamp = std::env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        Client::new(
+            url,
+            "abc123".to_string(),
+            RateLimiter::new(Duration::ZERO, stamp),
+        )
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(parse_response(CORRECT).unwrap(), Outcome::Correct);
+        assert_eq!(
+            parse_response(TOO_HIGH).unwrap(),
+            Outcome::Incorrect(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            parse_response(TOO_RECENT).unwrap(),
+            Outcome::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(parse_response(WRONG_LEVEL).unwrap(), Outcome::WrongLevel);
+        assert!(parse_response("<html>Puzzle inputs differ by user.</html>").is_err());
+    }
+
+    #[test]
+    fn test_correct_answer_is_recorded() {
+        let server = MockServer::start(vec![(200, CORRECT)]);
+        let client = client(&server.url);
+        let mut db = AnswerDb::default();
+
+        assert_eq!(
+            submit(&client, &mut db, 5, 2, "MCD").unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(db.accepted(5, "data", 2), Some("MCD"));
+        assert_eq!(
+            submit(&client, &mut db, 5, 2, "MCD").unwrap(),
+            Outcome::AlreadyCorrect
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/5/answer");
+        assert_eq!(requests[0].body, "level=2&answer=MCD");
+    }
+
+    #[test]
+    fn test_recorded_answer_is_still_submitted() {
+        let server = MockServer::start(vec![(200, CORRECT)]);
+        let client = client(&server.url);
+        let mut db = AnswerDb::default();
+        db.record(5, "data", 1, "CMZ".to_string());
+
+        assert_eq!(
+            submit(&client, &mut db, 5, 1, "CMZ").unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(db.accepted(5, "data", 1), Some("CMZ"));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn test_rejected_answer_is_not_resubmitted() {
+        let server = MockServer::start(vec![(200, TOO_HIGH), (200, TOO_RECENT)]);
+        let client = client(&server.url);
+        let mut db = AnswerDb::default();
+
+        assert_eq!(
+            submit(&client, &mut db, 1, 1, "80000").unwrap(),
+            Outcome::Incorrect(Some(Hint::TooHigh))
+        );
+        assert_eq!(
+            submit(&client, &mut db, 1, 1, "80000").unwrap(),
+            Outcome::PreviouslyRejected(Some(Hint::TooHigh))
+        );
+        assert!(matches!(
+            submit(&client, &mut db, 1, 1, "70000").unwrap(),
+            Outcome::RateLimited(_)
+        ));
+        assert_eq!(db.rejected(1, "data", 1).len(), 1);
+        assert_eq!(server.requests().len(), 2);
+    }
+}
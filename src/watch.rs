@@ -0,0 +1,189 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::error::AocError;
+use crate::input;
+
+/// How long the files must stay untouched before a rerun, so one save that writes several
+/// files (or an editor that writes twice) triggers a single run.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Collapses a burst of changes into one run once things have been quiet for `delay`.
+#[derive(Debug)]
+pub struct Debouncer {
+    delay: Duration,
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            last_change: None,
+        }
+    }
+
+    pub fn changed(&mut self, now: Instant) {
+        self.last_change = Some(now);
+    }
+
+    /// Time left before a pending change is due, if there is one.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.last_change
+            .map(|last| (last + self.delay).saturating_duration_since(now))
+    }
+
+    /// Whether a pending change is due; if so it is consumed.
+    pub fn ready(&mut self, now: Instant) -> bool {
+        if self.remaining(now) == Some(Duration::ZERO) {
+            self.last_change = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// The day's source directory and the directory holding its input. Directories are watched
+/// rather than files, since editors often save by replacing the file.
+pub fn watched_dirs(root: &Path, day: u8) -> Vec<PathBuf> {
+    let input = root.join(input::default_path(day));
+    let mut dirs = vec![root.join(format!("src/day{:02}", day))];
+    if let Some(dir) = input.parent() {
+        dirs.push(dir.to_path_buf());
+    }
+    dirs
+}
+
+/// Whether a change to `path` should rerun `day`: anything in its source directory except
+/// editor swap and backup files, or its input file.
+pub fn is_relevant(path: &Path, root: &Path, day: u8) -> bool {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    if name.starts_with('.') || name.ends_with('~') || name.ends_with(".swp") {
+        return false;
+    }
+
+    path.starts_with(root.join(format!("src/day{:02}", day)))
+        || path == root.join(input::default_path(day))
+}
+
+/// Rebuilds and reruns `day` whenever its source or input changes, until interrupted.
+pub fn watch(root: &Path, day: u8) -> Result<(), AocError> {
+    let root = root.canonicalize()?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(io::Error::other)?;
+    for dir in watched_dirs(&root, day) {
+        if dir.exists() {
+            watcher
+                .watch(&dir, RecursiveMode::Recursive)
+                .map_err(io::Error::other)?;
+        }
+    }
+
+    run_once(&root, day);
+    let mut debouncer = Debouncer::new(DEBOUNCE);
+    loop {
+        let timeout = debouncer
+            .remaining(Instant::now())
+            .unwrap_or(Duration::from_secs(60));
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) => {
+                let modifies = !matches!(event.kind, EventKind::Access(_));
+                if modifies && event.paths.iter().any(|p| is_relevant(p, &root, day)) {
+                    debouncer.changed(Instant::now());
+                }
+            }
+            Ok(Err(e)) => eprintln!("watch error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+
+        if debouncer.ready(Instant::now()) {
+            run_once(&root, day);
+        }
+    }
+}
+
+/// Clears the terminal, runs the day's tests and then the solver, both through cargo so
+/// the latest source is rebuilt first.
+fn run_once(root: &Path, day: u8) {
+    print!("\x1b[2J\x1b[H");
+    println!("day {:02}: rebuilding...", day);
+
+    let filter = format!("day{:02}::", day);
+    let tests = Command::new("cargo")
+        .args(["test", "--quiet", "--lib", &filter])
+        .current_dir(root)
+        .output();
+    match tests {
+        Ok(out) if out.status.success() => println!("day {:02}: tests passed", day),
+        Ok(out) => {
+            println!("day {:02}: tests FAILED", day);
+            print!("{}", String::from_utf8_lossy(&out.stdout));
+            print!("{}", String::from_utf8_lossy(&out.stderr));
+        }
+        Err(e) => println!("day {:02}: could not run cargo: {}", day, e),
+    }
+    println!();
+
+    let run = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--bin",
+            "aoc",
+            "--",
+            "run",
+            &day.to_string(),
+        ])
+        .current_dir(root)
+        .status();
+    if let Err(e) = run {
+        println!("day {:02}: could not run cargo: {}", day, e);
+    }
+    println!("\nwatching for changes (ctrl-c to stop)");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debouncer_waits_for_quiet() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(300));
+        assert!(!debouncer.ready(start));
+        assert_eq!(debouncer.remaining(start), None);
+
+        debouncer.changed(start);
+        debouncer.changed(start + Duration::from_millis(200));
+        assert!(!debouncer.ready(start + Duration::from_millis(400)));
+        assert_eq!(
+            debouncer.remaining(start + Duration::from_millis(400)),
+            Some(Duration::from_millis(100))
+        );
+        assert!(debouncer.ready(start + Duration::from_millis(500)));
+        assert!(!debouncer.ready(start + Duration::from_millis(900)));
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        let root = Path::new("/repo");
+        assert!(is_relevant(Path::new("/repo/src/day07/mod.rs"), root, 7));
+        assert!(is_relevant(Path::new("/repo/inputs/day07.txt"), root, 7));
+        assert!(!is_relevant(Path::new("/repo/inputs/day06.txt"), root, 7));
+        assert!(!is_relevant(
+            Path::new("/repo/src/day07/.mod.rs.swp"),
+            root,
+            7
+        ));
+        assert!(!is_relevant(Path::new("/repo/src/day07/mod.rs~"), root, 7));
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use advent_of_code_rust::answers::Verdict;
+use advent_of_code_rust::{cases, days};
+
+/// Runs every named input of every day against its `*.answers.json` sidecar.
+#[test]
+fn every_input_matches_its_expected_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut failures = vec![];
+
+    for day in days() {
+        let day_cases = cases::discover(root, day.number).unwrap();
+        assert!(
+            !day_cases.is_empty(),
+            "day {:02} has no inputs with expected answers",
+            day.number
+        );
+
+        for case in day_cases {
+            let verdicts = match cases::check(day, &case) {
+                Ok(verdicts) => verdicts,
+                Err(e) => {
+                    failures.push(format!("day {:02} {}: {}", day.number, case.name, e));
+                    continue;
+                }
+            };
+            for (part, verdict) in verdicts {
+                match verdict {
+                    Verdict::Match(_) => {}
+                    Verdict::Changed { expected, actual } => failures.push(format!(
+                        "day {:02} {} part {}: expected {} but got {}",
+                        day.number, case.name, part, expected, actual
+                    )),
+                    Verdict::Failed(e) => failures.push(format!(
+                        "day {:02} {} part {}: {}",
+                        day.number, case.name, part, e
+                    )),
+                    Verdict::New(_) => {
+                        unreachable!("only parts with an expected answer are checked")
+                    }
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
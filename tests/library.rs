@@ -0,0 +1,97 @@
+use advent_of_code_rust::day01::{find_max_calories, parse_elves};
+use advent_of_code_rust::day02::{GameOutcome, PlayChoice};
+use advent_of_code_rust::day04::Range;
+use advent_of_code_rust::day05::{parse_board_and_commands, Board, Command, TileElement};
+use advent_of_code_rust::day06::parse_marker_or_consume_char;
+use advent_of_code_rust::solution::{self, ParseMode};
+use advent_of_code_rust::{days, find_day, input, select_puzzles};
+
+#[test]
+fn domain_types_are_usable_outside_the_crate() {
+    let (_, elves) = parse_elves("1\n2\n\n4").unwrap();
+    assert_eq!(find_max_calories(&elves).unwrap().total_calories(), 4);
+
+    assert_eq!(PlayChoice::Rock.game(&PlayChoice::Paper), GameOutcome::Lose);
+    assert!(Range { start: 1, end: 6 }.fully_overlaps(&Range { start: 2, end: 3 }));
+    assert_eq!(
+        parse_marker_or_consume_char("bvwbjplbgvbhsrlpgdmjqwftvncz"),
+        Some(5)
+    );
+
+    let mut board = Board {
+        tiles: vec![vec![TileElement::new('Z'), TileElement::new('N')], vec![]],
+    };
+    Command {
+        size: 2,
+        from: 0,
+        to: 1,
+    }
+    .execute_v2(&mut board)
+    .unwrap();
+    assert_eq!(board.first_row(), "N");
+}
+
+#[test]
+fn solvers_run_through_the_registry() {
+    let (_, (board, commands)) =
+        parse_board_and_commands(include_str!("../src/day05/sample.txt")).unwrap();
+    assert_eq!(board.width(), 3);
+    assert_eq!(commands.len(), 4);
+
+    let day = find_day("day05").unwrap();
+    let answers = solution::solve(
+        day.solution,
+        include_str!("../src/day05/sample.txt"),
+        None,
+        ParseMode::Strict,
+    )
+    .unwrap();
+    assert_eq!(answers.part1.unwrap().unwrap(), "CMZ");
+    assert_eq!(answers.part2.unwrap().unwrap(), "MCD");
+    assert!(days().iter().any(|d| d.number == 5));
+}
+
+#[test]
+fn samples_survive_windows_line_endings_and_bom() {
+    let samples = [
+        include_str!("../src/day01/sample.txt"),
+        include_str!("../src/day02/sample.txt"),
+        include_str!("../src/day03/sample.txt"),
+        include_str!("../src/day04/sample.txt"),
+        include_str!("../src/day05/sample.txt"),
+        include_str!("../src/day06/sample.txt"),
+    ];
+
+    for (day, sample) in days().iter().zip(samples) {
+        let mangled = format!("\u{feff}{}\n\n", sample.replace('\n', "\r\n"));
+        let expected = solution::solve(day.solution, sample, None, ParseMode::Strict).unwrap();
+        let answers = solution::solve(
+            day.solution,
+            &input::normalize(&mangled),
+            None,
+            ParseMode::Strict,
+        )
+        .unwrap();
+        assert_eq!(
+            answers.part1.unwrap().unwrap(),
+            expected.part1.unwrap().unwrap(),
+            "day {}",
+            day.number
+        );
+        assert_eq!(
+            answers.part2.unwrap().unwrap(),
+            expected.part2.unwrap().unwrap(),
+            "day {}",
+            day.number
+        );
+    }
+}
+
+#[test]
+fn puzzles_are_selected_without_the_registry() {
+    assert!(find_day("7").is_none());
+    assert_eq!(select_puzzles("day07"), Some(vec![7]));
+    assert_eq!(select_puzzles("all").map(|d| d.len()), Some(25));
+    assert_eq!(select_puzzles("26"), None);
+    assert_eq!(select_puzzles("0"), None);
+}